@@ -11,6 +11,38 @@ use std::process::Stdio;
 #[cfg(feature = "native")]
 use tokio::process::Command;
 
+/// Credentials withheld from the gitleaks process
+///
+/// Gitleaks needs none of them, and the repository it scans may come from an
+/// untrusted fork.
+pub const WITHHELD_ENV_VARS: [&str; 6] = [
+    "GITHUB_TOKEN",
+    "GH_TOKEN",
+    "GITHUB_APP_PRIVATE_KEY",
+    "ACTIONS_RUNTIME_TOKEN",
+    "ACTIONS_ID_TOKEN_REQUEST_TOKEN",
+    "ACTIONS_ID_TOKEN_REQUEST_URL",
+];
+
+/// Rules used when the trusted commit has no gitleaks config
+const DEFAULT_RULES_CONFIG: &str = "[extend]\nuseDefault = true\n";
+
+/// Gitleaks config files gitleaks or the action pick up from a checkout
+const WORKSPACE_CONFIG_FILES: [&str; 2] = ["gitleaks.toml", ".gitleaks.toml"];
+
+/// Gitleaks config and ignore file read from a trusted commit
+///
+/// Passed explicitly so gitleaks does not load `.gitleaks.toml` and
+/// `.gitleaksignore` from an untrusted checkout, where a contributor could
+/// allowlist their own secret.
+#[derive(Debug, Clone)]
+pub struct TrustedSettings {
+    /// Passed with `--config`
+    pub config_path: PathBuf,
+    /// Passed with `--gitleaks-ignore-path`
+    pub ignore_path: PathBuf,
+}
+
 /// Platform information
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Platform {
@@ -362,8 +394,82 @@ pub async fn obtain_binary(config: &Config) -> Result<PathBuf> {
     download_binary(&version, platform, arch).await
 }
 
+/// Directory outside the workspace that holds the trusted settings
+pub fn trusted_settings_dir() -> PathBuf {
+    std::env::var_os("RUNNER_TEMP")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("secretscout-trusted")
+}
+
+/// Read the gitleaks config and ignore file of `commit` into `dir`
+///
+/// The config is the configured one, or the first of `gitleaks.toml` and
+/// `.gitleaks.toml`, as it exists at `commit`. Without one the default rules
+/// are used; without a `.gitleaksignore` nothing is ignored.
+#[cfg(feature = "native")]
+pub async fn load_trusted_settings(
+    config: &Config,
+    commit: &str,
+    dir: &Path,
+) -> Result<TrustedSettings> {
+    let mut candidates: Vec<String> = Vec::new();
+    if let Some(path) = &config.gitleaks_config {
+        let relative = path.strip_prefix(&config.workspace_path).unwrap_or(path);
+        candidates.push(relative.to_string_lossy().replace('\\', "/"));
+    }
+    candidates.extend(WORKSPACE_CONFIG_FILES.iter().map(|file| file.to_string()));
+
+    let mut rules = None;
+    for candidate in &candidates {
+        if let Some(contents) = show_file(config, commit, candidate).await {
+            log::info!("Using gitleaks config {} from {}", candidate, commit);
+            rules = Some(contents);
+            break;
+        }
+    }
+    let rules = rules.unwrap_or_else(|| {
+        log::info!("No gitleaks config at {}, using the default rules", commit);
+        DEFAULT_RULES_CONFIG.to_string()
+    });
+    let ignore = show_file(config, commit, ".gitleaksignore")
+        .await
+        .unwrap_or_default();
+
+    let settings = TrustedSettings {
+        config_path: dir.join("gitleaks.toml"),
+        ignore_path: dir.join(".gitleaksignore"),
+    };
+    let write = |path: &Path, contents: &str| {
+        std::fs::write(path, contents)
+            .map_err(|e| crate::error::Error::Io(format!("{}: {}", path.display(), e)))
+    };
+    std::fs::create_dir_all(dir)
+        .map_err(|e| crate::error::Error::Io(format!("{}: {}", dir.display(), e)))?;
+    write(&settings.config_path, &rules)?;
+    write(&settings.ignore_path, &ignore)?;
+
+    Ok(settings)
+}
+
+/// Contents of `path` at `commit`, if it exists there
+#[cfg(feature = "native")]
+async fn show_file(config: &Config, commit: &str, path: &str) -> Option<String> {
+    let object = format!("{}:{}", commit, path);
+    crate::events::git::run_git(&config.workspace_path, &["show", &object])
+        .await
+        .ok()
+}
+
 /// Build gitleaks command-line arguments
-pub fn build_arguments(config: &Config, log_opts: &str) -> Vec<String> {
+///
+/// With `trusted` settings, the config and ignore file from the checkout are
+/// never used.
+pub fn build_arguments(
+    config: &Config,
+    log_opts: &str,
+    trusted: Option<&TrustedSettings>,
+) -> Vec<String> {
     let mut args = vec![
         "detect".to_string(),
         "--redact".to_string(),
@@ -375,8 +481,19 @@ pub fn build_arguments(config: &Config, log_opts: &str) -> Vec<String> {
     ];
 
     // Add config file if specified
-    if let Some(ref config_path) = config.gitleaks_config {
-        args.push(format!("--config={}", config_path.display()));
+    match trusted {
+        Some(trusted) => {
+            args.push(format!("--config={}", trusted.config_path.display()));
+            args.push(format!(
+                "--gitleaks-ignore-path={}",
+                trusted.ignore_path.display()
+            ));
+        }
+        None => {
+            if let Some(ref config_path) = config.gitleaks_config {
+                args.push(format!("--config={}", config_path.display()));
+            }
+        }
     }
 
     // Add log-opts if specified
@@ -400,7 +517,12 @@ pub async fn execute_gitleaks(
         args.join(" ")
     );
 
-    let output = Command::new(binary_path)
+    let mut command = Command::new(binary_path);
+    for key in WITHHELD_ENV_VARS {
+        command.env_remove(key);
+    }
+
+    let output = command
         .args(args)
         .current_dir(workspace)
         .stdout(Stdio::piped())
@@ -480,24 +602,133 @@ mod tests {
     fn test_build_arguments() {
         let config = Config {
             github_token: String::new(),
+            github_app: None,
             gitleaks_license: None,
             gitleaks_version: "8.24.3".to_string(),
             gitleaks_config: None,
             enable_summary: true,
+            enable_annotations: true,
             enable_upload_artifact: true,
+            artifact_name: crate::config::DEFAULT_ARTIFACT_NAME.to_string(),
+            artifact_retention_days: None,
             enable_comments: true,
+            review_event: crate::github::ReviewEvent::Comment,
+            enable_check_run: false,
+            enable_commit_status: false,
+            issue_mode: crate::config::IssueMode::Off,
+            issue_labels: vec![],
+            enable_code_scanning_upload: false,
+            code_scanning_category: None,
             notify_user_list: Vec::new(),
+            notify_code_owners: false,
             base_ref: None,
+            dry_run: false,
             workspace_path: PathBuf::from("/workspace"),
             event_path: PathBuf::from("/workspace/event.json"),
             event_name: "push".to_string(),
             repository: "owner/repo".to_string(),
             repository_owner: "owner".to_string(),
+            sha: "abc123".to_string(),
+            git_ref: "refs/heads/main".to_string(),
+            run_id: String::new(),
+            github_api_url: "https://api.github.com".to_string(),
+            github_server_url: "https://github.com".to_string(),
+            github_graphql_url: "https://api.github.com/graphql".to_string(),
         };
 
-        let args = build_arguments(&config, "--no-merges");
+        let args = build_arguments(&config, "--no-merges", None);
         assert!(args.contains(&"detect".to_string()));
         assert!(args.contains(&"--redact".to_string()));
         assert!(args.contains(&"--log-opts=--no-merges".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_trusted_settings_ignore_the_checkout() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = dir.path().join("repo");
+        std::fs::create_dir(&repo).unwrap();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .args(args)
+                .current_dir(&repo)
+                .env("GIT_AUTHOR_NAME", "Jane")
+                .env("GIT_AUTHOR_EMAIL", "jane@example.com")
+                .env("GIT_COMMITTER_NAME", "Jane")
+                .env("GIT_COMMITTER_EMAIL", "jane@example.com")
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+            String::from_utf8(output.stdout).unwrap().trim().to_string()
+        };
+
+        git(&["init", "-q"]);
+        std::fs::write(repo.join(".gitleaksignore"), "base-fingerprint\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "base"]);
+        let base = git(&["rev-parse", "HEAD"]);
+
+        // The pull request allowlists everything and ignores its own secret
+        std::fs::write(
+            repo.join(".gitleaks.toml"),
+            "[allowlist]\npaths = [\".*\"]\n",
+        )
+        .unwrap();
+        std::fs::write(repo.join(".gitleaksignore"), "pr-fingerprint\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "head"]);
+        let head = git(&["rev-parse", "HEAD"]);
+
+        let mut config = Config::for_repository("owner/repo", repo.clone()).unwrap();
+        config.gitleaks_config = Some(repo.join(".gitleaks.toml"));
+        let trusted_dir = dir.path().join("trusted");
+        let trusted = load_trusted_settings(&config, &base, &trusted_dir)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&trusted.config_path).unwrap(),
+            DEFAULT_RULES_CONFIG
+        );
+        assert_eq!(
+            std::fs::read_to_string(&trusted.ignore_path).unwrap(),
+            "base-fingerprint\n"
+        );
+
+        let args = build_arguments(&config, "", Some(&trusted));
+        assert!(args.contains(&format!("--config={}", trusted.config_path.display())));
+        assert!(args.contains(&format!(
+            "--gitleaks-ignore-path={}",
+            trusted.ignore_path.display()
+        )));
+        assert!(!args.iter().any(|arg| arg.contains(".gitleaks.toml")));
+
+        // Once the config is on the trusted commit, it is used
+        let trusted = load_trusted_settings(&config, &head, &trusted_dir)
+            .await
+            .unwrap();
+        assert!(std::fs::read_to_string(&trusted.config_path)
+            .unwrap()
+            .contains("[allowlist]"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_execute_gitleaks_withholds_tokens() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let script = dir.path().join("gitleaks");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\necho \"token=$ACTIONS_ID_TOKEN_REQUEST_TOKEN\"\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        std::env::set_var("ACTIONS_ID_TOKEN_REQUEST_TOKEN", "secret-oidc-token");
+        let result = execute_gitleaks(&script, &[], dir.path()).await.unwrap();
+        std::env::remove_var("ACTIONS_ID_TOKEN_REQUEST_TOKEN");
+
+        assert_eq!(result.stdout.trim(), "token=");
+    }
 }
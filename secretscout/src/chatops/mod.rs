@@ -0,0 +1,501 @@
+//! ChatOps commands
+//!
+//! Maintainers drive SecretScout from pull request comments:
+//!
+//! - `/secretscout rescan` scans the pull request again
+//! - `/secretscout ignore <fingerprint> <reason>` acknowledges a false
+//!   positive, so later scans of the pull request leave it out
+//! - `/secretscout explain <fingerprint>` describes a finding
+//!
+//! Only users with write permission on the repository can run commands, and
+//! acknowledgements from anyone else are disregarded.
+
+use crate::config::{Config, IssueMode};
+use crate::error::Result;
+use crate::events::{self, EventContext, Repository};
+use crate::github;
+use crate::github::client::GitHubClient;
+use crate::github_actions::{self, ScanResult};
+use std::collections::{HashMap, HashSet};
+
+/// Prefix of every command
+const COMMAND_PREFIX: &str = "/secretscout";
+
+/// Usage shown for malformed commands
+const USAGE: &str = "Usage:\n\n\
+    - `/secretscout rescan`\n\
+    - `/secretscout ignore <fingerprint> <reason>`\n\
+    - `/secretscout explain <fingerprint>`";
+
+/// Findings listed in the rescan reply; the workflow run lists the rest
+pub const MAX_REPLY_FINDINGS: usize = 20;
+
+/// A command given in a pull request comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Rescan,
+    Ignore { fingerprint: String, reason: String },
+    Explain { fingerprint: String },
+}
+
+impl Command {
+    /// Parse the command in a comment
+    ///
+    /// The command must start a line. Returns `None` when the comment has no
+    /// command, and the reply explaining the problem when it is malformed.
+    pub fn parse(body: &str) -> Option<std::result::Result<Self, String>> {
+        let line = body.lines().map(str::trim).find(|line| {
+            line.strip_prefix(COMMAND_PREFIX)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        })?;
+
+        let mut words = line[COMMAND_PREFIX.len()..].split_whitespace();
+        let command = match (words.next(), words.next()) {
+            (Some("rescan"), None) => Ok(Command::Rescan),
+            (Some("ignore"), Some(fingerprint)) => {
+                let reason = words.collect::<Vec<_>>().join(" ");
+                if reason.is_empty() {
+                    Err("Please give a reason for ignoring the finding.".to_string())
+                } else {
+                    Ok(Command::Ignore {
+                        fingerprint: fingerprint.to_string(),
+                        reason,
+                    })
+                }
+            }
+            (Some("explain"), Some(fingerprint)) if words.next().is_none() => {
+                Ok(Command::Explain {
+                    fingerprint: fingerprint.to_string(),
+                })
+            }
+            _ => Err(format!("Unknown command `{}`.", line)),
+        };
+
+        Some(command.and_then(|command| match &command {
+            Command::Ignore { fingerprint, .. } | Command::Explain { fingerprint }
+                if Fingerprint::parse(fingerprint).is_none() =>
+            {
+                Err(format!(
+                    "`{}` is not a finding fingerprint. Fingerprints look like \
+                     `<commit>:<file>:<rule>:<line>`.",
+                    fingerprint
+                ))
+            }
+            _ => Ok(command),
+        }))
+    }
+}
+
+/// The parts of a finding fingerprint (`commit:file:rule:line`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint<'a> {
+    pub commit: &'a str,
+    pub file: &'a str,
+    pub rule: &'a str,
+    pub line: u32,
+}
+
+impl<'a> Fingerprint<'a> {
+    /// Split a fingerprint; the file may itself contain colons
+    pub fn parse(fingerprint: &'a str) -> Option<Self> {
+        let mut parts = fingerprint.rsplitn(3, ':');
+        let line = parts.next()?.parse().ok()?;
+        let rule = parts.next().filter(|rule| !rule.is_empty())?;
+        let (commit, file) = parts.next()?.split_once(':')?;
+
+        let is_sha = commit.len() >= 7 && commit.chars().all(|c| c.is_ascii_hexdigit());
+        if !is_sha || file.is_empty() {
+            return None;
+        }
+
+        Some(Fingerprint {
+            commit,
+            file,
+            rule,
+            line,
+        })
+    }
+}
+
+/// Handle the command in an `issue_comment` event
+///
+/// Returns the exit code of a rescan, or 0 for every other comment.
+pub async fn run(config: &Config) -> Result<i32> {
+    let event_json = events::read_event_file(&config.event_path)?;
+    if event_json["action"].as_str() != Some("created") {
+        log::info!("Ignoring comment event that did not create a comment");
+        return Ok(0);
+    }
+
+    let comment = events::parse_issue_comment(&event_json)?;
+    let command = match Command::parse(&comment.body) {
+        Some(command) if !comment.author_is_bot => command,
+        _ => {
+            log::info!("Comment is not a SecretScout command");
+            return Ok(0);
+        }
+    };
+    if !comment.on_pull_request {
+        log::info!("SecretScout commands only run on pull requests");
+        return Ok(0);
+    }
+
+    let repository = &events::parse_repository(&event_json, config)?;
+    let issue_number = comment.issue_number;
+    let client = &github::create_client(config).await?;
+    let reply = |body: String| async move {
+        github::create_issue_comment(config, client, repository, issue_number, &body).await
+    };
+
+    if !has_write_permission(config, client, repository, &comment.author).await? {
+        log::warn!(
+            "{} has no write permission, ignoring command",
+            comment.author
+        );
+        reply(format!(
+            "@{} only users with write access to this repository can run SecretScout commands.",
+            comment.author
+        ))
+        .await?;
+        return Ok(0);
+    }
+
+    let command = match command {
+        Ok(command) => command,
+        Err(problem) => {
+            reply(format!("{}\n\n{}", problem, USAGE)).await?;
+            return Ok(0);
+        }
+    };
+    log::info!("Running {:?} for {}", command, comment.author);
+
+    match command {
+        Command::Rescan => {
+            let event_context = events::parse_event_context(config).await?;
+            // GITHUB_SHA and GITHUB_REF name the default branch on
+            // issue_comment, so code scanning uploads go under the pull
+            // request head the context resolved, and default branch issues
+            // are left alone
+            let config = Config {
+                issue_mode: IssueMode::Off,
+                ..config.clone()
+            };
+
+            let result = github_actions::scan(&config, &event_context).await?;
+            // The scan has published its results, so a failed reply must not
+            // change its outcome
+            if let Err(e) = reply(build_rescan_reply(&config, &event_context, &result)).await {
+                log::warn!("Failed to reply to rescan command: {}", e);
+            }
+            Ok(result.exit_code)
+        }
+        Command::Ignore {
+            fingerprint,
+            reason,
+        } => {
+            reply(build_ignore_reply(&fingerprint, &reason, &comment.author)).await?;
+            Ok(0)
+        }
+        Command::Explain { fingerprint } => {
+            reply(build_explain_reply(repository, &fingerprint)).await?;
+            Ok(0)
+        }
+    }
+}
+
+/// Fingerprints acknowledged with `/secretscout ignore` on a pull request
+///
+/// Acknowledgements from users without write permission are disregarded.
+/// Errors are logged and treated as no acknowledgements.
+pub async fn acknowledged_fingerprints(
+    config: &Config,
+    client: &GitHubClient,
+    context: &EventContext,
+) -> HashSet<String> {
+    let Some(pr) = &context.pull_request else {
+        return HashSet::new();
+    };
+
+    let comments =
+        match github::fetch_issue_comments(config, client, &context.repository, pr.number as u64)
+            .await
+        {
+            Ok(comments) => comments,
+            Err(e) => {
+                log::warn!("Failed to fetch acknowledgements: {}", e);
+                return HashSet::new();
+            }
+        };
+
+    let mut permitted: HashMap<String, bool> = HashMap::new();
+    let mut acknowledged = HashSet::new();
+    for (author, fingerprint) in find_ignore_commands(&comments) {
+        if !permitted.contains_key(&author) {
+            let allowed = has_write_permission(config, client, &context.repository, &author)
+                .await
+                .unwrap_or_else(|e| {
+                    log::warn!("Could not check permission of {}: {}", author, e);
+                    false
+                });
+            permitted.insert(author.clone(), allowed);
+        }
+
+        if permitted[&author] {
+            acknowledged.insert(fingerprint);
+        } else {
+            log::warn!(
+                "Disregarding acknowledgement of {} by {}",
+                fingerprint,
+                author
+            );
+        }
+    }
+
+    acknowledged
+}
+
+/// Authors and fingerprints of the ignore commands among issue comments
+pub fn find_ignore_commands(comments: &[serde_json::Value]) -> Vec<(String, String)> {
+    comments
+        .iter()
+        .filter(|comment| comment["user"]["type"].as_str() != Some("Bot"))
+        .filter_map(|comment| {
+            let author = comment["user"]["login"].as_str()?;
+            match Command::parse(comment["body"].as_str()?)? {
+                Ok(Command::Ignore { fingerprint, .. }) => Some((author.to_string(), fingerprint)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Whether a user can push to the repository
+async fn has_write_permission(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    username: &str,
+) -> Result<bool> {
+    let permission = github::fetch_permission(config, client, repository, username).await?;
+    Ok(matches!(permission.as_str(), "admin" | "write"))
+}
+
+/// Reply to `/secretscout rescan`
+pub fn build_rescan_reply(config: &Config, context: &EventContext, result: &ScanResult) -> String {
+    let head = &context.head_ref[..context.head_ref.len().min(7)];
+
+    let mut body = if !result.findings.is_empty() {
+        let mut body = format!(
+            "🛑 Rescanned `{}`: {} secret(s) detected.\n\n",
+            head, result.findings_count
+        );
+        for finding in result.findings.iter().take(MAX_REPLY_FINDINGS) {
+            body.push_str(&format!(
+                "- `{}` in `{}:{}` (fingerprint `{}`)\n",
+                finding.rule_id, finding.file_path, finding.line_number, finding.fingerprint
+            ));
+        }
+        let unlisted = result.findings_count.saturating_sub(MAX_REPLY_FINDINGS);
+        if unlisted > 0 {
+            body.push_str(&format!(
+                "- …and {} more, listed in the workflow run\n",
+                unlisted
+            ));
+        }
+        body
+    } else if result.exit_code == 0 {
+        format!("✅ Rescanned `{}`: no secrets detected.\n", head)
+    } else {
+        format!(
+            "⚠️ Rescan of `{}` failed with exit code {}.\n",
+            head, result.exit_code
+        )
+    };
+
+    if let Some(run_url) = config.run_url() {
+        body.push_str(&format!("\n[Workflow run]({})\n", run_url));
+    }
+
+    body
+}
+
+/// Reply to `/secretscout ignore`
+pub fn build_ignore_reply(fingerprint: &str, reason: &str, author: &str) -> String {
+    format!(
+        "✅ @{} acknowledged `{}`: {}\n\n\
+         Later scans of this pull request will not report it. The finding is \
+         still reported on other branches unless its fingerprint is added to \
+         `.gitleaksignore`.\n",
+        author, fingerprint, reason
+    )
+}
+
+/// Reply to `/secretscout explain`
+pub fn build_explain_reply(repository: &Repository, fingerprint: &str) -> String {
+    let Some(parts) = Fingerprint::parse(fingerprint) else {
+        return format!("`{}` is not a finding fingerprint.\n", fingerprint);
+    };
+
+    format!(
+        "🔎 **Finding `{fingerprint}`**\n\n\
+         Gitleaks rule `{rule}` matched line {line} of [`{file}`]({url}/blob/{commit}/{file}#L{line}) \
+         in commit `{short}`. The matched value is redacted from every report.\n\n\
+         If it is a real secret, revoke and rotate it: deleting it in a later \
+         commit leaves it in the git history. If it is a false positive, \
+         comment `/secretscout ignore {fingerprint} <reason>` or add the \
+         fingerprint to `.gitleaksignore`.\n",
+        fingerprint = fingerprint,
+        rule = parts.rule,
+        line = parts.line,
+        file = parts.file,
+        url = repository.html_url,
+        commit = parts.commit,
+        short = &parts.commit[..7],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FINGERPRINT: &str = "abc123def456:config/app.env:generic-api-key:3";
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            Command::parse("/secretscout rescan"),
+            Some(Ok(Command::Rescan))
+        );
+        assert_eq!(
+            Command::parse(&format!(
+                "Looked into it.\n/secretscout ignore {} test fixture, not a real key",
+                FINGERPRINT
+            )),
+            Some(Ok(Command::Ignore {
+                fingerprint: FINGERPRINT.to_string(),
+                reason: "test fixture, not a real key".to_string(),
+            }))
+        );
+        assert_eq!(
+            Command::parse(&format!("/secretscout explain {}", FINGERPRINT)),
+            Some(Ok(Command::Explain {
+                fingerprint: FINGERPRINT.to_string(),
+            }))
+        );
+
+        assert_eq!(Command::parse("LGTM"), None);
+        assert_eq!(Command::parse("Run `/secretscout rescan` later"), None);
+        assert_eq!(Command::parse("/secretscouting rescan"), None);
+        assert!(matches!(Command::parse("/secretscout"), Some(Err(_))));
+        assert!(matches!(
+            Command::parse("/secretscout deploy"),
+            Some(Err(_))
+        ));
+        assert!(matches!(
+            Command::parse(&format!("/secretscout ignore {}", FINGERPRINT)),
+            Some(Err(_))
+        ));
+        assert!(matches!(
+            Command::parse("/secretscout explain not-a-fingerprint"),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_fingerprint() {
+        assert_eq!(
+            Fingerprint::parse("abc123def456:dir/a:b.txt:aws-access-token:42"),
+            Some(Fingerprint {
+                commit: "abc123def456",
+                file: "dir/a:b.txt",
+                rule: "aws-access-token",
+                line: 42,
+            })
+        );
+        assert_eq!(Fingerprint::parse("abc123def456:file.txt:rule:x"), None);
+        assert_eq!(Fingerprint::parse("xyz:file.txt:rule:1"), None);
+    }
+
+    #[test]
+    fn test_find_ignore_commands() {
+        let comments = vec![
+            serde_json::json!({
+                "user": { "login": "maintainer", "type": "User" },
+                "body": format!("/secretscout ignore {} fixture", FINGERPRINT),
+            }),
+            serde_json::json!({
+                "user": { "login": "github-actions[bot]", "type": "Bot" },
+                "body": format!("/secretscout ignore {} echoed", FINGERPRINT),
+            }),
+            serde_json::json!({
+                "user": { "login": "maintainer", "type": "User" },
+                "body": "/secretscout rescan",
+            }),
+        ];
+
+        assert_eq!(
+            find_ignore_commands(&comments),
+            [("maintainer".to_string(), FINGERPRINT.to_string())]
+        );
+    }
+
+    #[test]
+    fn test_build_rescan_reply() {
+        let mut config =
+            Config::for_repository("owner/repo", std::path::PathBuf::from(".")).unwrap();
+        config.run_id = "42".to_string();
+        let context = EventContext {
+            event_type: events::EventType::IssueComment,
+            repository: Repository {
+                owner: "owner".to_string(),
+                name: "repo".to_string(),
+                full_name: "owner/repo".to_string(),
+                html_url: "https://github.com/owner/repo".to_string(),
+            },
+            base_ref: "abc123".to_string(),
+            head_ref: "def4567890".to_string(),
+            commits: Vec::new(),
+            pull_request: None,
+            inputs: None,
+        };
+        let finding = crate::sarif::types::DetectedSecret {
+            rule_id: "generic-api-key".to_string(),
+            file_path: "config/app.env".to_string(),
+            line_number: 3,
+            commit_sha: "abc123def456".to_string(),
+            author: "Jane".to_string(),
+            email: "jane@example.com".to_string(),
+            date: "2025-10-16".to_string(),
+            fingerprint: FINGERPRINT.to_string(),
+            owners: Vec::new(),
+        };
+        let result = ScanResult {
+            exit_code: 1,
+            findings: vec![finding; github_actions::MAX_RETAINED_FINDINGS],
+            findings_count: 1500,
+        };
+
+        let reply = build_rescan_reply(&config, &context, &result);
+        assert!(reply.starts_with("🛑 Rescanned `def4567`: 1500 secret(s) detected."));
+        assert_eq!(reply.matches("(fingerprint").count(), MAX_REPLY_FINDINGS);
+        assert!(reply.contains("…and 1480 more, listed in the workflow run"));
+        assert!(reply.contains("(https://github.com/owner/repo/actions/runs/42)"));
+    }
+
+    #[test]
+    fn test_build_explain_reply() {
+        let repository = Repository {
+            owner: "owner".to_string(),
+            name: "repo".to_string(),
+            full_name: "owner/repo".to_string(),
+            html_url: "https://github.com/owner/repo".to_string(),
+        };
+
+        let reply = build_explain_reply(&repository, FINGERPRINT);
+        assert!(reply.contains("Gitleaks rule `generic-api-key` matched line 3"));
+        assert!(
+            reply.contains("(https://github.com/owner/repo/blob/abc123def456/config/app.env#L3)")
+        );
+        assert!(reply.contains("in commit `abc123d`"));
+    }
+}
@@ -1,5 +1,7 @@
 //! CLI argument parsing and command execution
 
+use crate::config::{Config, DEFAULT_API_URL, DEFAULT_SERVER_URL};
+use crate::error::{ConfigError, Result};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -17,6 +19,39 @@ pub struct Cli {
     /// Path to gitleaks config file
     #[arg(short, long, global = true)]
     pub config: Option<PathBuf>,
+
+    /// GitHub REST API URL (GitHub Enterprise Server: https://HOST/api/v3)
+    #[arg(long, global = true, env = "GITHUB_API_URL", default_value = DEFAULT_API_URL)]
+    pub github_api_url: String,
+
+    /// GitHub web URL used for generated links
+    #[arg(long, global = true, env = "GITHUB_SERVER_URL", default_value = DEFAULT_SERVER_URL)]
+    pub github_server_url: String,
+
+    /// GitHub GraphQL API URL (derived from the REST API URL by default)
+    #[arg(long, global = true, env = "GITHUB_GRAPHQL_URL")]
+    pub github_graphql_url: Option<String>,
+
+    /// GitHub token for commands that report to GitHub
+    #[arg(long, global = true, env = "GITHUB_TOKEN", hide_env_values = true)]
+    pub github_token: Option<String>,
+
+    /// GitHub App id, to authenticate as an app instead of with a token
+    #[arg(long, global = true, env = "GITHUB_APP_ID")]
+    pub github_app_id: Option<String>,
+
+    /// GitHub App private key (PEM contents or path to a PEM file)
+    #[arg(
+        long,
+        global = true,
+        env = "GITHUB_APP_PRIVATE_KEY",
+        hide_env_values = true
+    )]
+    pub github_app_private_key: Option<String>,
+
+    /// GitHub App installation id (looked up from the repository by default)
+    #[arg(long, global = true, env = "GITHUB_APP_INSTALLATION_ID")]
+    pub github_app_installation_id: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -50,6 +85,22 @@ pub enum Commands {
         /// Enable verbose logging
         #[arg(short, long)]
         verbose: bool,
+
+        /// Set a secretscout/scan commit status on GitHub (needs a token or GitHub App)
+        #[arg(long, requires = "repository")]
+        github_status: bool,
+
+        /// Repository in owner/repo format that receives the commit status
+        #[arg(long, env = "GITHUB_REPOSITORY")]
+        repository: Option<String>,
+
+        /// Commit that receives the status (default: HEAD of the source)
+        #[arg(long)]
+        sha: Option<String>,
+
+        /// URL the commit status links to, such as the CI build
+        #[arg(long)]
+        target_url: Option<String>,
     },
 
     /// Protect staged changes
@@ -67,12 +118,162 @@ pub enum Commands {
         verbose: bool,
     },
 
+    /// Convert a report between formats without rescanning
+    Convert {
+        /// Input format (sarif, gitleaks-json, csv)
+        #[arg(long)]
+        from: String,
+
+        /// Output format (sarif, json, csv, junit, markdown, html, gitlab)
+        #[arg(long)]
+        to: String,
+
+        /// Path to the report to convert
+        input: PathBuf,
+
+        /// Path to write the converted report
+        output: PathBuf,
+
+        /// Repository URL used for links in markdown and html reports
+        #[arg(long)]
+        repository_url: Option<String>,
+
+        /// Repository in owner/repo format, linked on the GitHub server URL
+        #[arg(long, conflicts_with = "repository_url")]
+        repository: Option<String>,
+
+        /// Repository checkout whose CODEOWNERS file attributes the findings
+        #[arg(long)]
+        source: Option<PathBuf>,
+
+        /// Group markdown and html reports by code owner
+        #[arg(long)]
+        group_by_owner: bool,
+    },
+
+    /// Run the GitHub Action for a saved event payload
+    Action {
+        /// Event payload to replay, as found at GITHUB_EVENT_PATH
+        #[arg(long)]
+        event_file: PathBuf,
+
+        /// Event name (push, pull_request, workflow_dispatch, ...)
+        #[arg(long)]
+        event_name: String,
+
+        /// Repository in owner/repo format the event belongs to
+        #[arg(long, env = "GITHUB_REPOSITORY")]
+        repository: String,
+
+        /// Checkout of the repository to scan
+        #[arg(short, long, default_value = ".")]
+        source: PathBuf,
+
+        /// Commit to scan (default: the payload's head commit, then HEAD of the source)
+        #[arg(long)]
+        sha: Option<String>,
+
+        /// Print comments, summary, statuses and uploads instead of making them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Publish a check run with line annotations
+        #[arg(long)]
+        check_run: bool,
+
+        /// Set a secretscout/scan commit status
+        #[arg(long)]
+        commit_status: bool,
+
+        /// Upload the SARIF report to code scanning
+        #[arg(long)]
+        code_scanning_upload: bool,
+    },
+
+    /// Work with SARIF reports
+    Sarif {
+        #[command(subcommand)]
+        command: SarifCommands,
+    },
+
     /// Print version information
     Version,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum SarifCommands {
+    /// Validate a SARIF report against the SARIF 2.1.0 schema and GitHub's upload limits
+    Validate {
+        /// Path to SARIF report
+        #[arg(default_value = "results.sarif")]
+        path: PathBuf,
+    },
+}
+
 impl Cli {
     pub fn parse_args() -> Self {
         Cli::parse()
     }
+
+    /// Build the configuration for reporting to `repository` on GitHub
+    ///
+    /// Fails unless a token or GitHub App credentials are given.
+    pub fn github_config(&self, repository: &str, workspace_path: PathBuf) -> Result<Config> {
+        let mut config = Config::for_repository(repository, workspace_path)?;
+        self.apply_github_settings(&mut config)?;
+        config.github_token = self.github_token.clone().unwrap_or_default();
+
+        if config.github_token.is_empty() && config.github_app.is_none() {
+            return Err(ConfigError::MissingEnvVar(
+                "--github-token (GITHUB_TOKEN) or --github-app-id (GITHUB_APP_ID)".into(),
+            )
+            .into());
+        }
+
+        Ok(config)
+    }
+
+    /// Build the configuration for replaying an event with `secretscout action`
+    ///
+    /// A dry run only reads from GitHub, so it also works without credentials
+    /// on public repositories.
+    pub fn action_config(
+        &self,
+        repository: &str,
+        workspace_path: PathBuf,
+        dry_run: bool,
+    ) -> Result<Config> {
+        let mut config = if dry_run {
+            let mut config = Config::for_repository(repository, workspace_path)?;
+            self.apply_github_settings(&mut config)?;
+            config.github_token = self.github_token.clone().unwrap_or_default();
+            config
+        } else {
+            self.github_config(repository, workspace_path)?
+        };
+        // Like the action, pick up gitleaks.toml from the checkout by default
+        config.gitleaks_config = self.config.clone().or_else(|| {
+            let default_config = config.workspace_path.join("gitleaks.toml");
+            default_config.exists().then_some(default_config)
+        });
+
+        Ok(config)
+    }
+
+    /// Apply the GitHub URLs and App credentials given on the command line
+    pub fn apply_github_settings(&self, config: &mut Config) -> Result<()> {
+        config.github_api_url = self.github_api_url.trim_end_matches('/').to_string();
+        config.github_server_url = self.github_server_url.trim_end_matches('/').to_string();
+        config.github_graphql_url = match &self.github_graphql_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => Config::default_graphql_url(&config.github_api_url),
+        };
+        config.github_app = Config::parse_github_app(
+            self.github_app_id.clone(),
+            self.github_app_private_key.clone(),
+            self.github_app_installation_id.clone(),
+        )?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,259 @@
+//! CODEOWNERS parsing
+//!
+//! Maps file paths to their owners using GitHub's CODEOWNERS rules: patterns
+//! follow gitignore syntax and the last matching line wins.
+
+use crate::config::Config;
+use crate::events::git::run_git;
+use crate::events::EventContext;
+use crate::sarif::types::DetectedSecret;
+use std::path::Path;
+
+/// Locations GitHub reads CODEOWNERS from, in order of precedence
+const CODEOWNERS_PATHS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Parsed CODEOWNERS file
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: String,
+    owners: Vec<String>,
+}
+
+impl CodeOwners {
+    /// Load the CODEOWNERS file of a checkout, if it has one
+    pub fn load(workspace: &Path) -> Option<Self> {
+        CODEOWNERS_PATHS.iter().find_map(|path| {
+            let contents = std::fs::read_to_string(workspace.join(path)).ok()?;
+            log::debug!("Loaded code owners from {}", path);
+            Some(Self::parse(&contents))
+        })
+    }
+
+    /// Load the CODEOWNERS file that applies to an event
+    ///
+    /// Runs with a base branch token check out the pull request, whose author
+    /// must not choose who is notified or assigned, so the file is read from
+    /// the base commit for them rather than from the checkout.
+    pub async fn for_event(config: &Config, context: &EventContext) -> Option<Self> {
+        if !context.has_base_token() {
+            return Self::load(&config.workspace_path);
+        }
+
+        for path in CODEOWNERS_PATHS {
+            let object = format!("{}:{}", context.base_ref, path);
+            let shown = run_git(&config.workspace_path, &["show", &object]).await;
+            if let Ok(contents) = shown {
+                log::debug!("Loaded code owners from {} at {}", path, context.base_ref);
+                return Some(Self::parse(&contents));
+            }
+        }
+        None
+    }
+
+    /// Parse the contents of a CODEOWNERS file
+    pub fn parse(contents: &str) -> Self {
+        let rules = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let line = line.split(" #").next().unwrap_or(line);
+                let mut fields = line.split_whitespace();
+                let pattern = fields.next()?.to_string();
+                let owners = fields.map(str::to_string).collect();
+                Some(Rule { pattern, owners })
+            })
+            .collect();
+
+        CodeOwners { rules }
+    }
+
+    /// Owners of a repository-relative path (`@user`, `@org/team` or emails)
+    ///
+    /// Empty when no rule matches, or when the last matching rule lists no
+    /// owners.
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        let path = path.trim_start_matches("./").trim_start_matches('/');
+
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| pattern_matches(&rule.pattern, path))
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Attach the owners of each finding's file to the finding
+    pub fn assign_owners(&self, findings: &mut [DetectedSecret]) {
+        for finding in findings {
+            finding.owners = self.owners_of(&finding.file_path).to_vec();
+        }
+    }
+}
+
+/// Match a gitignore-style CODEOWNERS pattern against a path
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let directory_only = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+
+    // Patterns with a slash before the end are relative to the root
+    let glob = if trimmed.starts_with('/') {
+        trimmed.trim_start_matches('/').to_string()
+    } else if trimmed.contains('/') {
+        trimmed.to_string()
+    } else {
+        format!("**/{}", trimmed)
+    };
+
+    if !directory_only && glob_matches(glob.as_bytes(), path.as_bytes()) {
+        return true;
+    }
+
+    // A pattern naming a directory also matches everything below it, but
+    // `docs/*` matches only the files directly in `docs`
+    let last_segment = glob.rsplit('/').next().unwrap_or_default();
+    !last_segment.contains('*') && glob_matches(format!("{}/**", glob).as_bytes(), path.as_bytes())
+}
+
+/// Match a glob where `*` and `?` stay within a path segment and `**`
+/// crosses segments
+fn glob_matches(pattern: &[u8], text: &[u8]) -> bool {
+    if let Some(rest) = pattern.strip_prefix(b"**/") {
+        return glob_matches(rest, text)
+            || (0..text.len()).any(|i| text[i] == b'/' && glob_matches(rest, &text[i + 1..]));
+    }
+
+    if let Some(rest) = pattern.strip_prefix(b"**") {
+        return (0..=text.len()).any(|i| glob_matches(rest, &text[i..]));
+    }
+
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(b'*') => {
+            let segment_end = text.iter().position(|&c| c == b'/').unwrap_or(text.len());
+            (0..=segment_end).any(|i| glob_matches(&pattern[1..], &text[i..]))
+        }
+        Some(b'?') => {
+            text.first().is_some_and(|&c| c != b'/') && glob_matches(&pattern[1..], &text[1..])
+        }
+        Some(c) => text.first() == Some(c) && glob_matches(&pattern[1..], &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEOWNERS: &str = "\
+# Default owners
+*       @org/everyone
+
+*.rs    @rustacean    # Rust files
+/docs/  @docs-team jane@example.com
+src/config/ @config-owner
+**/secrets/** @security
+/build/logs/
+";
+
+    #[test]
+    fn test_owners_of() {
+        let owners = CodeOwners::parse(CODEOWNERS);
+
+        assert_eq!(owners.owners_of("README.md"), ["@org/everyone"]);
+        assert_eq!(owners.owners_of("src/main.rs"), ["@rustacean"]);
+        assert_eq!(
+            owners.owners_of("docs/guide/setup.md"),
+            ["@docs-team", "jane@example.com"]
+        );
+        assert_eq!(owners.owners_of("src/config/mod.rs"), ["@config-owner"]);
+        assert_eq!(owners.owners_of("deploy/secrets/prod.env"), ["@security"]);
+        assert!(owners.owners_of("build/logs/out.txt").is_empty());
+    }
+
+    #[test]
+    fn test_assign_owners() {
+        let owners = CodeOwners::parse(CODEOWNERS);
+        let mut findings = vec![DetectedSecret {
+            rule_id: "generic-api-key".to_string(),
+            file_path: "src/config/mod.rs".to_string(),
+            line_number: 1,
+            commit_sha: "abc123".to_string(),
+            author: "Jane".to_string(),
+            email: "jane@example.com".to_string(),
+            date: "2025-10-16".to_string(),
+            fingerprint: "abc123:src/config/mod.rs:generic-api-key:1".to_string(),
+            owners: Vec::new(),
+        }];
+
+        owners.assign_owners(&mut findings);
+        assert_eq!(findings[0].owners, ["@config-owner"]);
+    }
+
+    #[tokio::test]
+    async fn test_for_event_reads_the_base_commit() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .env("GIT_AUTHOR_NAME", "Jane")
+                .env("GIT_AUTHOR_EMAIL", "jane@example.com")
+                .env("GIT_COMMITTER_NAME", "Jane")
+                .env("GIT_COMMITTER_EMAIL", "jane@example.com")
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+            String::from_utf8(output.stdout).unwrap().trim().to_string()
+        };
+        git(&["init", "-q"]);
+        std::fs::create_dir(dir.path().join(".github")).unwrap();
+        std::fs::write(dir.path().join(".github/CODEOWNERS"), "* @maintainer\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "base"]);
+        let base = git(&["rev-parse", "HEAD"]);
+        // The pull request rewrites CODEOWNERS in the checkout
+        std::fs::write(dir.path().join(".github/CODEOWNERS"), "* @attacker\n").unwrap();
+
+        let config = Config::for_repository("owner/repo", dir.path().to_path_buf()).unwrap();
+        let mut context = EventContext {
+            event_type: crate::events::EventType::PullRequestTarget,
+            repository: crate::events::Repository {
+                owner: "owner".to_string(),
+                name: "repo".to_string(),
+                full_name: "owner/repo".to_string(),
+                html_url: "https://github.com/owner/repo".to_string(),
+            },
+            base_ref: base,
+            head_ref: "head".to_string(),
+            commits: Vec::new(),
+            pull_request: None,
+            inputs: None,
+        };
+
+        let owners = CodeOwners::for_event(&config, &context).await.unwrap();
+        assert_eq!(owners.owners_of("src/main.rs"), ["@maintainer"]);
+
+        context.event_type = crate::events::EventType::PullRequest;
+        let owners = CodeOwners::for_event(&config, &context).await.unwrap();
+        assert_eq!(owners.owners_of("src/main.rs"), ["@attacker"]);
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches("*.js", "a/b/c.js"));
+        assert!(!pattern_matches("*.js", "a/b/c.jsx"));
+        assert!(pattern_matches("/docs/", "docs/a.md"));
+        assert!(!pattern_matches("/docs/", "src/docs/a.md"));
+        assert!(pattern_matches("docs/*", "docs/a.md"));
+        assert!(!pattern_matches("docs/*", "docs/nested/a.md"));
+        assert!(pattern_matches("apps/", "x/apps/y.txt"));
+        assert!(pattern_matches("a/**/b.txt", "a/b.txt"));
+        assert!(pattern_matches("a/**/b.txt", "a/x/y/b.txt"));
+        assert!(pattern_matches("?.txt", "x.txt"));
+    }
+}
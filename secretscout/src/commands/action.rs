@@ -0,0 +1,103 @@
+//! Action command - replay a GitHub Actions event locally
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::events::{self, git, EventType};
+use crate::github_actions;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Run the action for a saved event payload, as the workflow would
+///
+/// `config` carries the repository, workspace and toggles; the event fields
+/// are filled in here. The commit defaults to the one the payload names and
+/// then to HEAD of the workspace. With `dry_run` nothing is written to
+/// GitHub, the summary, outputs and API writes are printed instead.
+pub async fn action(
+    mut config: Config,
+    event_file: &Path,
+    event_name: &str,
+    sha: Option<&str>,
+    dry_run: bool,
+) -> Result<i32> {
+    EventType::from_str(event_name)?;
+    let event_json = events::read_event_file(event_file)?;
+
+    let (event_sha, event_ref) = event_commit(&event_json);
+    config.sha = match sha.or(event_sha.as_deref()) {
+        Some(sha) => sha.to_string(),
+        None => git::run_git(&config.workspace_path, &["rev-parse", "HEAD"])
+            .await?
+            .trim()
+            .to_string(),
+    };
+    config.git_ref = match event_ref {
+        Some(git_ref) => git_ref,
+        None => git::run_git(
+            &config.workspace_path,
+            &["rev-parse", "--symbolic-full-name", "HEAD"],
+        )
+        .await
+        .map(|r| r.trim().to_string())
+        .unwrap_or_default(),
+    };
+    config.event_path = event_file.to_path_buf();
+    config.event_name = event_name.to_string();
+    config.dry_run = dry_run;
+
+    if dry_run {
+        log::info!("Dry run: writes to GitHub are printed instead of made");
+    }
+
+    github_actions::run(&config).await
+}
+
+/// Commit and ref named by an event payload, when it has them
+fn event_commit(event_json: &serde_json::Value) -> (Option<String>, Option<String>) {
+    let text =
+        |value: &serde_json::Value| value.as_str().filter(|s| !s.is_empty()).map(str::to_string);
+
+    if let Some(pr) = event_json.get("pull_request") {
+        let git_ref = pr["number"]
+            .as_u64()
+            .map(|number| format!("refs/pull/{}/merge", number));
+        return (text(&pr["head"]["sha"]), git_ref);
+    }
+
+    let sha = text(&event_json["after"]).filter(|sha| !events::is_null_sha(sha));
+    (sha, text(&event_json["ref"]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_commit() {
+        let push = serde_json::json!({
+            "ref": "refs/heads/main",
+            "before": "a".repeat(40),
+            "after": "b".repeat(40),
+        });
+        assert_eq!(
+            event_commit(&push),
+            (Some("b".repeat(40)), Some("refs/heads/main".to_string()))
+        );
+
+        let pull_request = serde_json::json!({
+            "pull_request": { "number": 7, "head": { "sha": "c".repeat(40) } },
+        });
+        assert_eq!(
+            event_commit(&pull_request),
+            (Some("c".repeat(40)), Some("refs/pull/7/merge".to_string()))
+        );
+
+        let deleted = serde_json::json!({ "ref": "refs/heads/gone", "after": "0".repeat(40) });
+        assert_eq!(
+            event_commit(&deleted),
+            (None, Some("refs/heads/gone".to_string()))
+        );
+
+        assert_eq!(event_commit(&serde_json::json!({})), (None, None));
+    }
+}
@@ -0,0 +1,50 @@
+//! Convert command - re-render an existing report in another format
+
+use crate::codeowners::CodeOwners;
+use crate::error::{ReportError, Result};
+use crate::report::{self, InputFormat, OutputFormat, WriteOptions};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+pub fn convert(
+    from: &str,
+    to: &str,
+    input: &Path,
+    output: &Path,
+    repository_url: Option<String>,
+    source: Option<&Path>,
+    group_by_owner: bool,
+) -> Result<()> {
+    let input_format: InputFormat = from.parse()?;
+    let output_format: OutputFormat = to.parse()?;
+
+    let mut findings = report::read_findings(input_format, input)?;
+
+    if let Some(source) = source {
+        match CodeOwners::load(source) {
+            Some(owners) => owners.assign_owners(&mut findings),
+            None => log::warn!("No CODEOWNERS file found in {}", source.display()),
+        }
+    }
+
+    let file = File::create(output)
+        .map_err(|e| ReportError::WriteFailed(format!("{}: {}", output.display(), e)))?;
+    let mut writer = BufWriter::new(file);
+
+    let options = WriteOptions {
+        repository_url,
+        group_by_owner,
+    };
+    report::write_findings(output_format, &findings, &options, &mut writer)?;
+
+    println!(
+        "Converted {} finding(s) from {} to {}: {}",
+        findings.len(),
+        from,
+        to,
+        output.display()
+    );
+
+    Ok(())
+}
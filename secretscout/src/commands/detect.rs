@@ -1,8 +1,20 @@
 //! Detect command - scan repository for secrets
 
-use crate::{binary, error::Result};
+use crate::config::Config;
+use crate::events::{git, Repository};
+use crate::outputs::{self, ScanOutcome};
+use crate::report::{self, InputFormat};
+use crate::{binary, error::Result, github};
 use std::path::Path;
 
+/// Commit status to set on GitHub once the scan finishes
+pub struct GitHubStatus {
+    pub config: Config,
+    /// Commit that receives the status; HEAD of the source when unset
+    pub sha: Option<String>,
+    pub target_url: Option<String>,
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn detect(
     source: &Path,
@@ -13,6 +25,7 @@ pub async fn detect(
     log_opts: Option<&str>,
     config_path: Option<&Path>,
     verbose: bool,
+    github_status: Option<&GitHubStatus>,
 ) -> Result<()> {
     // Ensure gitleaks binary is available
     let platform = binary::Platform::detect()?;
@@ -59,6 +72,15 @@ pub async fn detect(
     // Execute gitleaks
     let result = binary::execute_gitleaks(&gitleaks_path, &args, source).await?;
 
+    if let Some(status) = github_status {
+        let outcome = match result.exit_code {
+            0 => ScanOutcome::Clean,
+            2 => ScanOutcome::Leaks(count_findings(report_format, report_path)),
+            _ => ScanOutcome::Failed,
+        };
+        publish_status(status, source, outcome).await?;
+    }
+
     match result.exit_code {
         0 => {
             println!("No secrets detected");
@@ -75,3 +97,52 @@ pub async fn detect(
         }
     }
 }
+
+/// Set the commit status for the scan result
+async fn publish_status(status: &GitHubStatus, source: &Path, outcome: ScanOutcome) -> Result<()> {
+    let sha = match &status.sha {
+        Some(sha) => sha.clone(),
+        None => git::run_git(source, &["rev-parse", "HEAD"])
+            .await?
+            .trim()
+            .to_string(),
+    };
+
+    let (owner, name) = status.config.repo_parts();
+    let repository = Repository {
+        owner: owner.to_string(),
+        name: name.to_string(),
+        full_name: status.config.repository.clone(),
+        html_url: status.config.repository_url(&status.config.repository),
+    };
+
+    let client = github::create_client(&status.config).await?;
+    outputs::publish_commit_status(
+        &status.config,
+        &client,
+        &repository,
+        &sha,
+        outcome,
+        status.target_url.as_deref(),
+    )
+    .await?;
+
+    println!(
+        "Set {} commit status on {}",
+        outputs::commit_status::STATUS_CONTEXT,
+        sha
+    );
+    Ok(())
+}
+
+/// Count the findings in the report, if its format can be read back
+fn count_findings(report_format: &str, report_path: &Path) -> Option<usize> {
+    let format = match report_format {
+        "json" => InputFormat::GitleaksJson,
+        other => other.parse().ok()?,
+    };
+
+    report::read_findings(format, report_path)
+        .ok()
+        .map(|findings| findings.len())
+}
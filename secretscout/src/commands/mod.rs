@@ -1,7 +1,12 @@
 //! Command implementations for CLI
 
+pub mod action;
+pub mod convert;
 pub mod detect;
 pub mod protect;
+pub mod sarif;
 
+pub use action::action;
+pub use convert::convert;
 pub use detect::detect;
 pub use protect::protect;
@@ -0,0 +1,27 @@
+//! SARIF command - inspect and validate SARIF reports
+
+use crate::{error::Result, sarif};
+use std::path::Path;
+
+/// Validate a SARIF report and print every problem found
+///
+/// Returns the process exit code: 0 when the report is valid, 1 otherwise.
+pub fn validate(path: &Path) -> Result<i32> {
+    let errors = sarif::validate_sarif(path)?;
+
+    if errors.is_empty() {
+        println!("{} is a valid SARIF 2.1.0 report", path.display());
+        return Ok(0);
+    }
+
+    eprintln!(
+        "{} has {} validation error(s):",
+        path.display(),
+        errors.len()
+    );
+    for error in &errors {
+        eprintln!("  {}", error);
+    }
+
+    Ok(1)
+}
@@ -37,6 +37,9 @@ pub enum Error {
     #[error("GitHub API error: {0}")]
     GitHub(#[from] GitHubError),
 
+    #[error("Report error: {0}")]
+    Report(#[from] ReportError),
+
     #[error("I/O error: {0}")]
     Io(String),
 
@@ -106,6 +109,15 @@ pub enum EventError {
 
     #[error("Invalid PR number: {0}")]
     InvalidPRNumber(i64),
+
+    #[error("Git command failed: {0}")]
+    GitFailed(String),
+
+    #[error("Scan range is not in the clone: {0} (check out with fetch-depth: 0)")]
+    HistoryUnavailable(String),
+
+    #[error("Invalid workflow_dispatch input {name}: {value}")]
+    InvalidInput { name: String, value: String },
 }
 
 /// Binary management errors
@@ -163,6 +175,24 @@ pub enum SarifError {
     NoResults,
 }
 
+/// Report conversion errors
+#[derive(thiserror::Error, Debug)]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub enum ReportError {
+    #[error("Unsupported {direction} format: {format} (expected one of: {expected})")]
+    UnsupportedFormat {
+        direction: String,
+        format: String,
+        expected: String,
+    },
+
+    #[error("Failed to read report: {0}")]
+    ReadFailed(String),
+
+    #[error("Failed to write report: {0}")]
+    WriteFailed(String),
+}
+
 /// GitHub API errors
 #[derive(thiserror::Error, Debug)]
 #[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
@@ -193,6 +223,12 @@ pub enum GitHubError {
 
     #[error("Max retries exceeded")]
     MaxRetriesExceeded,
+
+    #[error("SARIF upload rejected by code scanning: {0}")]
+    SarifRejected(String),
+
+    #[error("Artifact upload rejected: {0}")]
+    ArtifactRejected(String),
 }
 
 impl Error {
@@ -212,6 +248,7 @@ impl Error {
             Error::Binary(BinaryError::GitleaksError { .. }) => ErrorSeverity::Fatal,
             Error::Sarif(SarifError::FileNotFound(_)) => ErrorSeverity::Fatal,
             Error::Sarif(SarifError::ParseError(_)) => ErrorSeverity::Fatal,
+            Error::Report(_) => ErrorSeverity::Fatal,
             Error::GitHub(GitHubError::AuthenticationFailed(_)) => ErrorSeverity::Fatal,
             Error::License(_) => ErrorSeverity::Fatal,
 
@@ -284,13 +321,6 @@ impl From<reqwest::Error> for Error {
     }
 }
 
-#[cfg(feature = "native")]
-impl From<octocrab::Error> for Error {
-    fn from(err: octocrab::Error) -> Self {
-        Error::GitHub(GitHubError::NetworkError(err.to_string()))
-    }
-}
-
 /// Result type alias for SecretScout operations
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -0,0 +1,634 @@
+//! Local git queries
+//!
+//! Used when the GitHub API cannot describe the scan range, for example for
+//! pull requests with more commits than the API lists, and to deepen shallow
+//! clones until the scan range is present.
+
+use super::{Author, Commit, EventContext, EventType};
+use crate::binary::WITHHELD_ENV_VARS;
+use crate::error::{EventError, Result};
+use std::path::Path;
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Remote `actions/checkout` fetches from
+const REMOTE: &str = "origin";
+
+/// Commits fetched by the first deepening step; each step doubles it
+const INITIAL_DEEPEN: u32 = 64;
+
+/// Deepening steps before falling back to fetching the full history
+const MAX_DEEPEN_STEPS: u32 = 6;
+
+/// Separates fields of a commit in `git log` output
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// Separates commits in `git log` output
+const RECORD_SEPARATOR: char = '\u{1e}';
+
+/// List the commits in `base..head`, oldest first
+///
+/// Follows first parents only, matching the range gitleaks scans.
+pub async fn list_commits(workspace: &Path, base_sha: &str, head_sha: &str) -> Result<Vec<Commit>> {
+    let range = format!("{}..{}", base_sha, head_sha);
+    let output = run_git(
+        workspace,
+        &[
+            "log",
+            "--reverse",
+            "--first-parent",
+            "--format=%H%x1f%an%x1f%ae%x1f%B%x1e",
+            &range,
+        ],
+    )
+    .await?;
+
+    Ok(parse_log(&output))
+}
+
+/// History a scan needs in the local clone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum History {
+    /// Only the checked out commit
+    Head,
+    /// Everything from `head` back to where it meets `base`
+    Range { base: String, head: String },
+    /// Commits since a date
+    Since { date: String, head: String },
+    /// The complete history
+    Full,
+}
+
+impl History {
+    /// History the scan of an event reads, following [`super::build_log_opts`]
+    pub fn of(context: &EventContext) -> Self {
+        let range = |base: &str| History::Range {
+            base: base.to_string(),
+            head: context.head_ref.clone(),
+        };
+
+        match context.event_type {
+            EventType::Push if context.base_ref.is_empty() => History::Full,
+            EventType::Push if context.base_ref == context.head_ref => History::Head,
+            // The scan includes the first PR commit, so its parent is needed
+            EventType::PullRequest => range(&format!("{}^", context.base_ref)),
+            EventType::Push
+            | EventType::PullRequestTarget
+            | EventType::IssueComment
+            | EventType::MergeGroup => range(&context.base_ref),
+            EventType::WorkflowDispatch => {
+                let Some(inputs) = &context.inputs else {
+                    return History::Full;
+                };
+                let head = if context.head_ref.is_empty() {
+                    "HEAD".to_string()
+                } else {
+                    context.head_ref.clone()
+                };
+
+                match &inputs.since {
+                    _ if !context.base_ref.is_empty() => History::Range {
+                        base: context.base_ref.clone(),
+                        head,
+                    },
+                    Some(date) => History::Since {
+                        date: date.clone(),
+                        head,
+                    },
+                    None => History::Full,
+                }
+            }
+            EventType::Schedule => History::Full,
+        }
+    }
+}
+
+/// Whether the clone is shallow (has a `.git/shallow` file)
+pub async fn is_shallow(workspace: &Path) -> Result<bool> {
+    let output = run_git(workspace, &["rev-parse", "--is-shallow-repository"]).await?;
+    Ok(output.trim() == "true")
+}
+
+/// Deepen a shallow clone until it holds the history a scan needs
+///
+/// Ranges are deepened with `git fetch --deepen`, doubling the depth each
+/// time, before fetching the full history. Complete clones are left alone.
+pub async fn ensure_history(workspace: &Path, history: &History) -> Result<()> {
+    match is_shallow(workspace).await {
+        Ok(true) => {}
+        Ok(false) => return Ok(()),
+        Err(e) => {
+            // Leave it to gitleaks to report a workspace that is not a clone
+            log::debug!("Could not check for a shallow clone: {}", e);
+            return Ok(());
+        }
+    }
+
+    match history {
+        History::Head => Ok(()),
+        History::Full => {
+            log::info!("Shallow clone, fetching the full history");
+            fetch(workspace, &["--unshallow"], &[]).await
+        }
+        History::Since { date, head } => {
+            log::info!("Shallow clone, fetching history since {}", date);
+            let since = format!("--shallow-since={}", date);
+            fetch(workspace, &[&since], &[head]).await
+        }
+        History::Range { base, head } => deepen_to(workspace, base, head).await,
+    }
+}
+
+/// Deepen until `base` and `head` share history
+async fn deepen_to(workspace: &Path, base: &str, head: &str) -> Result<()> {
+    if range_complete(workspace, base, head).await {
+        return Ok(());
+    }
+
+    // A base that is not an ancestor of the head, such as the tip of a pull
+    // request's base branch, has to be fetched itself
+    let base_sha = base.trim_end_matches('^');
+    if let Err(e) = fetch_commit(workspace, base_sha).await {
+        log::debug!("Could not fetch {} directly: {}", base_sha, e);
+    }
+
+    let mut depth = INITIAL_DEEPEN;
+    for _ in 0..MAX_DEEPEN_STEPS {
+        if !is_shallow(workspace).await? {
+            break;
+        }
+        log::info!(
+            "Shallow clone, deepening by {} commits to reach {}",
+            depth,
+            base
+        );
+        let deepen = format!("--deepen={}", depth);
+        fetch(workspace, &[&deepen], &[head]).await?;
+        if range_complete(workspace, base, head).await {
+            return Ok(());
+        }
+        depth *= 2;
+    }
+
+    if is_shallow(workspace).await? {
+        log::info!("Fetching the full history to reach {}", base);
+        fetch(workspace, &["--unshallow"], &[head]).await?;
+        if range_complete(workspace, base, head).await {
+            return Ok(());
+        }
+    }
+
+    Err(EventError::HistoryUnavailable(format!(
+        "{} is not reachable from {} even with the full history of {}",
+        base, head, REMOTE
+    ))
+    .into())
+}
+
+/// Whether `base` and `head` are present and meet in the local history
+async fn range_complete(workspace: &Path, base: &str, head: &str) -> bool {
+    run_git(workspace, &["merge-base", base, head])
+        .await
+        .is_ok()
+}
+
+/// Fetch from the checkout's remote
+///
+/// The local `HEAD` means nothing to the remote, so it is fetched through
+/// the remote's configured refs instead.
+async fn fetch(workspace: &Path, options: &[&str], revisions: &[&str]) -> Result<()> {
+    let mut args = vec!["fetch", "--no-tags", "--quiet"];
+    args.extend(options);
+    args.push(REMOTE);
+    args.extend(revisions.iter().filter(|revision| **revision != "HEAD"));
+    run_git(workspace, &args).await.map(drop)
+}
+
+/// Fetch a commit by SHA unless it is already in the local clone
+///
+/// GitHub serves commits that no ref points to any more, such as the
+/// previous tip of a force-pushed branch. Shallow clones fetch just the
+/// commit, leaving its history to [`ensure_history`].
+pub async fn fetch_commit(workspace: &Path, sha: &str) -> Result<()> {
+    if commit_exists(workspace, sha).await {
+        return Ok(());
+    }
+
+    log::info!("Fetching commit {}", sha);
+    if is_shallow(workspace).await? {
+        fetch(workspace, &["--depth=1"], &[sha]).await
+    } else {
+        fetch(workspace, &[], &[sha]).await
+    }
+}
+
+/// Resolve a branch, tag or commit to a commit SHA, fetching it if needed
+///
+/// Checkouts hold only the checked out branch and no tags, so other branches
+/// and tags are fetched from the remote by name.
+pub async fn resolve_revision(workspace: &Path, revision: &str) -> Result<String> {
+    if let Ok(sha) = rev_parse(workspace, revision).await {
+        return Ok(sha);
+    }
+
+    log::info!("Fetching {}", revision);
+    if is_shallow(workspace).await? {
+        fetch(workspace, &["--depth=1"], &[revision]).await?;
+    } else {
+        fetch(workspace, &[], &[revision]).await?;
+    }
+    rev_parse(workspace, "FETCH_HEAD").await
+}
+
+/// SHA of the commit a revision names in the local clone
+async fn rev_parse(workspace: &Path, revision: &str) -> Result<String> {
+    let object = format!("{}^{{commit}}", revision);
+    let output = run_git(workspace, &["rev-parse", "--verify", "--quiet", &object]).await?;
+    Ok(output.trim().to_string())
+}
+
+/// Whether a commit is present in the local clone
+pub async fn commit_exists(workspace: &Path, sha: &str) -> bool {
+    let object = format!("{}^{{commit}}", sha);
+    run_git(workspace, &["cat-file", "-e", &object])
+        .await
+        .is_ok()
+}
+
+/// Run a git command in the workspace and return its stdout
+pub async fn run_git(workspace: &Path, args: &[&str]) -> Result<String> {
+    log::debug!("Running git {}", args.join(" "));
+
+    let mut command = Command::new("git");
+    for key in WITHHELD_ENV_VARS {
+        command.env_remove(key);
+    }
+
+    let output = command
+        .args(args)
+        .current_dir(workspace)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(|e| EventError::GitFailed(format!("failed to spawn git: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(EventError::GitFailed(format!(
+            "git {} exited with {}: {}",
+            args.join(" "),
+            output.status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+        .into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn parse_log(output: &str) -> Vec<Commit> {
+    output
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(4, FIELD_SEPARATOR);
+            let sha = fields.next().filter(|sha| !sha.is_empty())?;
+
+            Some(Commit {
+                sha: sha.to_string(),
+                author: Author {
+                    name: fields.next().unwrap_or("unknown").to_string(),
+                    email: fields.next().unwrap_or("unknown").to_string(),
+                },
+                message: fields.next().unwrap_or_default().trim_end().to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Jane")
+            .env("GIT_AUTHOR_EMAIL", "jane@example.com")
+            .env("GIT_COMMITTER_NAME", "Jane")
+            .env("GIT_COMMITTER_EMAIL", "jane@example.com")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let output = "aaa\u{1f}Jane\u{1f}jane@example.com\u{1f}First\n\nBody\n\u{1e}\n\
+                      bbb\u{1f}John\u{1f}john@example.com\u{1f}Second\n\u{1e}\n";
+
+        let commits = parse_log(output);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].sha, "aaa");
+        assert_eq!(commits[0].message, "First\n\nBody");
+        assert_eq!(commits[1].author.email, "john@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_list_commits() {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        git(dir.path(), &["commit", "-q", "--allow-empty", "-m", "base"]);
+        let base = git(dir.path(), &["rev-parse", "HEAD"]);
+        git(dir.path(), &["commit", "-q", "--allow-empty", "-m", "one"]);
+        git(dir.path(), &["commit", "-q", "--allow-empty", "-m", "two"]);
+        let head = git(dir.path(), &["rev-parse", "HEAD"]);
+
+        let commits = list_commits(dir.path(), &base, &head).await.unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].message, "one");
+        assert_eq!(commits[1].sha, head);
+
+        assert!(list_commits(dir.path(), "0000000", &head).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_commit_exists() {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        git(dir.path(), &["commit", "-q", "--allow-empty", "-m", "base"]);
+        let head = git(dir.path(), &["rev-parse", "HEAD"]);
+
+        assert!(commit_exists(dir.path(), &head).await);
+        assert!(!commit_exists(dir.path(), &"1".repeat(40)).await);
+    }
+
+    /// Commit in `dir` with a fixed date
+    fn commit_at(dir: &Path, message: &str, date: &str) -> String {
+        let status = std::process::Command::new("git")
+            .args(["commit", "-q", "--allow-empty", "-m", message])
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Jane")
+            .env("GIT_AUTHOR_EMAIL", "jane@example.com")
+            .env("GIT_COMMITTER_NAME", "Jane")
+            .env("GIT_COMMITTER_EMAIL", "jane@example.com")
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        git(dir, &["rev-parse", "HEAD"])
+    }
+
+    /// A bare origin with `main` and a `feature` branch forked from it, and a
+    /// depth 1 clone of `feature` over file://
+    struct Remote {
+        _dir: TempDir,
+        clone: std::path::PathBuf,
+        first: String,
+        main: String,
+        feature: String,
+    }
+
+    fn shallow_clone() -> Remote {
+        let dir = TempDir::new().unwrap();
+        let work = dir.path().join("work");
+        std::fs::create_dir(&work).unwrap();
+        git(&work, &["init", "-q", "-b", "main"]);
+
+        let first = commit_at(&work, "first", "2024-01-01T00:00:00Z");
+        for day in 2..=9 {
+            commit_at(&work, "main", &format!("2024-01-0{}T00:00:00Z", day));
+        }
+        git(&work, &["checkout", "-q", "-b", "feature"]);
+        for day in 10..=12 {
+            commit_at(&work, "feature", &format!("2024-01-{}T00:00:00Z", day));
+        }
+        let feature = git(&work, &["rev-parse", "HEAD"]);
+        git(&work, &["checkout", "-q", "main"]);
+        let main = commit_at(&work, "main after fork", "2024-01-13T00:00:00Z");
+
+        let origin = dir.path().join("origin.git");
+        git(
+            dir.path(),
+            &[
+                "clone",
+                "-q",
+                "--bare",
+                work.to_str().unwrap(),
+                "origin.git",
+            ],
+        );
+        let url = format!("file://{}", origin.display());
+        git(
+            dir.path(),
+            &[
+                "clone",
+                "-q",
+                "--depth=1",
+                "--branch=feature",
+                &url,
+                "clone",
+            ],
+        );
+
+        Remote {
+            clone: dir.path().join("clone"),
+            _dir: dir,
+            first,
+            main,
+            feature,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_revision() {
+        let remote = shallow_clone();
+        let origin = remote.clone.parent().unwrap().join("origin.git");
+        git(&origin, &["tag", "v1.0.0", &remote.first]);
+
+        // The checked out branch, then a branch and a tag only the remote has
+        for (revision, sha) in [
+            ("HEAD", &remote.feature),
+            ("main", &remote.main),
+            ("v1.0.0", &remote.first),
+        ] {
+            let resolved = resolve_revision(&remote.clone, revision).await.unwrap();
+            assert_eq!(&resolved, sha, "{}", revision);
+        }
+        assert!(resolve_revision(&remote.clone, "missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ensure_history_deepens_to_base() {
+        let remote = shallow_clone();
+        assert!(is_shallow(&remote.clone).await.unwrap());
+        assert!(!commit_exists(&remote.clone, &remote.first).await);
+
+        // Push range whose base is an ancestor of the head
+        let history = History::Range {
+            base: remote.first.clone(),
+            head: remote.feature.clone(),
+        };
+        ensure_history(&remote.clone, &history).await.unwrap();
+        assert!(commit_exists(&remote.clone, &remote.first).await);
+        let commits = list_commits(&remote.clone, &remote.first, &remote.feature)
+            .await
+            .unwrap();
+        assert_eq!(commits.len(), 11);
+    }
+
+    #[tokio::test]
+    async fn test_ensure_history_fetches_diverged_base() {
+        let remote = shallow_clone();
+
+        // Pull request base branch tip that is not in the head's history
+        let history = History::Range {
+            base: remote.main.clone(),
+            head: remote.feature.clone(),
+        };
+        ensure_history(&remote.clone, &history).await.unwrap();
+        let commits = list_commits(&remote.clone, &remote.main, &remote.feature)
+            .await
+            .unwrap();
+        assert_eq!(commits.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_ensure_history_since_and_full() {
+        let remote = shallow_clone();
+
+        let history = History::Since {
+            date: "2024-01-07".to_string(),
+            head: remote.feature.clone(),
+        };
+        ensure_history(&remote.clone, &history).await.unwrap();
+        assert!(is_shallow(&remote.clone).await.unwrap());
+        assert!(!commit_exists(&remote.clone, &remote.first).await);
+        let log = run_git(&remote.clone, &["rev-list", "--count", "HEAD"])
+            .await
+            .unwrap();
+        assert!(log.trim().parse::<u32>().unwrap() >= 5);
+
+        ensure_history(&remote.clone, &History::Full).await.unwrap();
+        assert!(!is_shallow(&remote.clone).await.unwrap());
+        assert!(commit_exists(&remote.clone, &remote.first).await);
+    }
+
+    #[tokio::test]
+    async fn test_ensure_history_unreachable_base() {
+        let remote = shallow_clone();
+
+        let history = History::Range {
+            base: "1".repeat(40),
+            head: remote.feature.clone(),
+        };
+        let err = ensure_history(&remote.clone, &history).await.unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::Error::Event(EventError::HistoryUnavailable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_force_push_fetches_previous_tip() {
+        let remote = shallow_clone();
+        let config =
+            crate::config::Config::for_repository("owner/repo", remote.clone.clone()).unwrap();
+        let repository = crate::events::Repository {
+            owner: "owner".to_string(),
+            name: "repo".to_string(),
+            full_name: "owner/repo".to_string(),
+            html_url: "https://github.com/owner/repo".to_string(),
+        };
+
+        // The previous tip is on the remote but not in the depth 1 clone
+        assert!(!commit_exists(&remote.clone, &remote.main).await);
+        let event = serde_json::json!({
+            "ref": "refs/heads/feature",
+            "before": remote.main,
+            "after": remote.feature,
+            "forced": true,
+            "commits": [],
+        });
+        let context = crate::events::parse_push_event(&event, repository.clone(), &config)
+            .await
+            .unwrap();
+        assert_eq!(context.base_ref, remote.main);
+
+        ensure_history(&remote.clone, &History::of(&context))
+            .await
+            .unwrap();
+        let commits = list_commits(&remote.clone, &remote.main, &remote.feature)
+            .await
+            .unwrap();
+        assert_eq!(commits.len(), 3);
+
+        // A previous tip the remote does not have falls back to a full scan
+        let event = serde_json::json!({
+            "ref": "refs/heads/feature",
+            "before": "1".repeat(40),
+            "after": remote.feature,
+            "forced": true,
+            "commits": [],
+        });
+        let context = crate::events::parse_push_event(&event, repository, &config)
+            .await
+            .unwrap();
+        assert_eq!(context.base_ref, "");
+    }
+
+    #[test]
+    fn test_history_of() {
+        let mut context = EventContext {
+            event_type: EventType::Push,
+            repository: crate::events::Repository {
+                owner: "owner".to_string(),
+                name: "repo".to_string(),
+                full_name: "owner/repo".to_string(),
+                html_url: "https://github.com/owner/repo".to_string(),
+            },
+            base_ref: "abc123".to_string(),
+            head_ref: "def456".to_string(),
+            commits: Vec::new(),
+            pull_request: None,
+            inputs: None,
+        };
+        assert_eq!(
+            History::of(&context),
+            History::Range {
+                base: "abc123".to_string(),
+                head: "def456".to_string()
+            }
+        );
+
+        context.event_type = EventType::PullRequest;
+        assert_eq!(
+            History::of(&context),
+            History::Range {
+                base: "abc123^".to_string(),
+                head: "def456".to_string()
+            }
+        );
+
+        context.event_type = EventType::Push;
+        context.base_ref = String::new();
+        assert_eq!(History::of(&context), History::Full);
+
+        context.event_type = EventType::WorkflowDispatch;
+        context.head_ref = String::new();
+        context.inputs = Some(crate::events::DispatchInputs {
+            since: Some("2025-01-31".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            History::of(&context),
+            History::Since {
+                date: "2025-01-31".to_string(),
+                head: "HEAD".to_string()
+            }
+        );
+    }
+}
@@ -1,7 +1,8 @@
 //! Event routing and processing module
 //!
 //! This module handles GitHub event parsing and routing for all supported
-//! event types: push, pull_request, workflow_dispatch, and schedule.
+//! event types: push, pull_request, pull_request_target, merge_group,
+//! workflow_dispatch, schedule, and issue_comment.
 
 use crate::config::Config;
 use crate::error::{EventError, Result};
@@ -9,14 +10,26 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::str::FromStr;
 
+#[cfg(feature = "native")]
+pub mod git;
+
+/// Most commits the pull request commits API lists
+pub const PR_COMMITS_API_LIMIT: usize = 250;
+
+/// Most commits a push event payload lists
+pub const PUSH_COMMITS_PAYLOAD_LIMIT: usize = 20;
+
 /// Supported GitHub event types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EventType {
     Push,
     PullRequest,
+    PullRequestTarget,
+    MergeGroup,
     WorkflowDispatch,
     Schedule,
+    IssueComment,
 }
 
 /// Complete event context with all necessary information for scanning
@@ -28,6 +41,52 @@ pub struct EventContext {
     pub head_ref: String,
     pub commits: Vec<Commit>,
     pub pull_request: Option<PullRequest>,
+    /// Scan customization from `workflow_dispatch` inputs
+    #[serde(default)]
+    pub inputs: Option<DispatchInputs>,
+}
+
+/// Scan customization read from the inputs of a `workflow_dispatch` event
+///
+/// The `base_ref` and `head_ref` inputs become the context's range; these are
+/// the remaining inputs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DispatchInputs {
+    /// Only scan commits newer than this date
+    pub since: Option<String>,
+    /// Only scan changes to these paths
+    pub paths: Vec<String>,
+    /// Extra report format to write next to the SARIF report
+    pub report_format: Option<String>,
+    /// When the scan fails the workflow
+    pub fail_on: FailOn,
+}
+
+/// When a scan fails the workflow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailOn {
+    /// Fail on findings and on scan errors
+    #[default]
+    Findings,
+    /// Report findings without failing, fail only on scan errors
+    Error,
+}
+
+impl FromStr for FailOn {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "findings" => Ok(FailOn::Findings),
+            "error" => Ok(FailOn::Error),
+            _ => Err(EventError::InvalidInput {
+                name: "fail_on".to_string(),
+                value: s.to_string(),
+            }
+            .into()),
+        }
+    }
 }
 
 /// Repository information
@@ -60,6 +119,23 @@ pub struct PullRequest {
     pub number: i64,
     pub base: GitReference,
     pub head: GitReference,
+    /// Head branch lives in a fork of the repository
+    #[serde(default)]
+    pub fork: bool,
+}
+
+/// Comment on an issue or pull request, from an `issue_comment` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueComment {
+    pub id: u64,
+    pub body: String,
+    /// Login of the commenter
+    pub author: String,
+    /// Commenter is a bot, such as the one posting SecretScout's replies
+    pub author_is_bot: bool,
+    pub issue_number: u64,
+    /// The issue is a pull request
+    pub on_pull_request: bool,
 }
 
 /// Git reference (branch or tag)
@@ -69,6 +145,64 @@ pub struct GitReference {
     pub ref_name: String,
 }
 
+impl EventContext {
+    /// Whether the event is a pull request, from any trigger
+    pub fn is_pull_request(&self) -> bool {
+        matches!(
+            self.event_type,
+            EventType::PullRequest | EventType::PullRequestTarget | EventType::IssueComment
+        )
+    }
+
+    /// Whether the workflow token is read-only
+    ///
+    /// GitHub gives `pull_request` workflows of fork PRs a read-only token,
+    /// so nothing can be written back through the API.
+    pub fn is_read_only(&self) -> bool {
+        self.event_type == EventType::PullRequest
+            && self.pull_request.as_ref().is_some_and(|pr| pr.fork)
+    }
+
+    /// Whether untrusted pull request code is scanned with a write token
+    ///
+    /// `pull_request_target` and comment commands run with the base
+    /// repository's token while the workspace holds the pull request head, so
+    /// nothing in the checkout may influence the scan's outcome.
+    pub fn has_base_token(&self) -> bool {
+        matches!(
+            self.event_type,
+            EventType::PullRequestTarget | EventType::IssueComment
+        )
+    }
+
+    /// Commit that was scanned, which check runs and commit statuses are set on
+    ///
+    /// Pull requests use their head commit and other events the head they
+    /// resolved, such as a dispatch's `head_ref` input. Only when the event
+    /// names no head (schedules, dispatches without inputs) is `GITHUB_SHA`
+    /// used.
+    pub fn head_sha<'a>(&'a self, config: &'a Config) -> &'a str {
+        match &self.pull_request {
+            Some(pr) => &pr.head.sha,
+            None if !self.head_ref.is_empty() => &self.head_ref,
+            None => &config.sha,
+        }
+    }
+
+    /// Commit and ref that code scanning results are uploaded under
+    ///
+    /// `GITHUB_SHA` and `GITHUB_REF` name the base branch on
+    /// `pull_request_target` and the default branch on comment commands, so
+    /// pull requests are uploaded under their head commit and
+    /// `refs/pull/<number>/head` instead.
+    pub fn code_scanning_target(&self, config: &Config) -> (String, String) {
+        match &self.pull_request {
+            Some(pr) => (pr.head.sha.clone(), format!("refs/pull/{}/head", pr.number)),
+            None => (config.sha.clone(), config.git_ref.clone()),
+        }
+    }
+}
+
 impl std::str::FromStr for EventType {
     type Err = crate::error::Error;
 
@@ -76,8 +210,11 @@ impl std::str::FromStr for EventType {
         match s {
             "push" => Ok(EventType::Push),
             "pull_request" => Ok(EventType::PullRequest),
+            "pull_request_target" => Ok(EventType::PullRequestTarget),
+            "merge_group" => Ok(EventType::MergeGroup),
             "workflow_dispatch" => Ok(EventType::WorkflowDispatch),
             "schedule" => Ok(EventType::Schedule),
+            "issue_comment" => Ok(EventType::IssueComment),
             _ => Err(EventError::UnsupportedEvent(s.to_string()).into()),
         }
     }
@@ -99,13 +236,19 @@ pub async fn parse_event_context(config: &Config) -> Result<EventContext> {
     match event_type {
         EventType::Push => parse_push_event(&event_json, repository, config).await,
         EventType::PullRequest => parse_pull_request_event(&event_json, repository, config).await,
-        EventType::WorkflowDispatch => parse_workflow_dispatch_event(repository),
+        EventType::PullRequestTarget => parse_pull_request_target_event(&event_json, repository),
+        EventType::MergeGroup => parse_merge_group_event(&event_json, repository, config),
+        EventType::WorkflowDispatch => {
+            let context = parse_workflow_dispatch_event(&event_json, repository)?;
+            resolve_dispatch_refs(context, config).await
+        }
         EventType::Schedule => parse_schedule_event(repository),
+        EventType::IssueComment => parse_issue_comment_event(&event_json, repository, config).await,
     }
 }
 
 /// Read event JSON file
-fn read_event_file(path: &Path) -> Result<serde_json::Value> {
+pub(crate) fn read_event_file(path: &Path) -> Result<serde_json::Value> {
     let contents = std::fs::read_to_string(path)
         .map_err(|e| EventError::InvalidEventJson(format!("Failed to read event file: {}", e)))?;
 
@@ -114,7 +257,10 @@ fn read_event_file(path: &Path) -> Result<serde_json::Value> {
 }
 
 /// Parse repository from event JSON
-fn parse_repository(event_json: &serde_json::Value, config: &Config) -> Result<Repository> {
+pub(crate) fn parse_repository(
+    event_json: &serde_json::Value,
+    config: &Config,
+) -> Result<Repository> {
     // Try to extract repository from event
     if let Some(repo_obj) = event_json.get("repository") {
         let owner = repo_obj["owner"]["login"]
@@ -150,7 +296,7 @@ fn parse_repository(event_json: &serde_json::Value, config: &Config) -> Result<R
         let name = full_name
             .trim_start_matches(&format!("{}/", owner))
             .to_string();
-        let html_url = format!("https://github.com/{}", full_name);
+        let html_url = config.repository_url(&full_name);
 
         Ok(Repository {
             owner,
@@ -162,43 +308,88 @@ fn parse_repository(event_json: &serde_json::Value, config: &Config) -> Result<R
 }
 
 /// Parse push event
+///
+/// The scan range is `before..after` from the payload. The listed commits are
+/// not used for the range: the payload lists at most 20 and none for tag
+/// pushes. A push that creates a branch or tag has no previous commit, so
+/// everything reachable from `after` is scanned. The previous commit of a
+/// force push is fetched from the remote when it is not in the clone, and
+/// everything reachable from `after` is scanned only if that fails.
 #[cfg(feature = "native")]
 async fn parse_push_event(
     event_json: &serde_json::Value,
     repository: Repository,
     config: &Config,
 ) -> Result<EventContext> {
-    let commits_array = event_json["commits"]
-        .as_array()
-        .ok_or_else(|| EventError::MissingField("commits".to_string()))?;
+    let field = |name: &str| {
+        event_json[name]
+            .as_str()
+            .ok_or_else(|| EventError::MissingField(name.to_string()))
+    };
+
+    let git_ref = event_json["ref"].as_str().unwrap_or_default();
+    let before = field("before")?;
+    let after = field("after")?;
 
-    if commits_array.is_empty() {
+    if event_json["deleted"].as_bool() == Some(true) || is_null_sha(after) {
+        log::info!("Push deleted {}, nothing to scan", git_ref);
         return Err(EventError::NoCommits.into());
     }
 
-    let commits: Vec<Commit> = commits_array.iter().filter_map(parse_commit).collect();
+    let commits: Vec<Commit> = event_json["commits"]
+        .as_array()
+        .map(|commits| commits.iter().filter_map(parse_commit).collect())
+        .unwrap_or_default();
 
-    if commits.is_empty() {
-        return Err(EventError::NoCommits.into());
+    if commits.len() >= PUSH_COMMITS_PAYLOAD_LIMIT {
+        log::info!(
+            "Push payload lists only {} commits, scanning the full range",
+            commits.len()
+        );
     }
 
-    // Determine base and head refs
-    let base_ref = config
-        .base_ref
-        .clone()
-        .unwrap_or_else(|| commits[0].sha.clone());
-    let head_ref = commits.last().unwrap().sha.clone();
+    let base_ref = if let Some(base_ref) = &config.base_ref {
+        base_ref.clone()
+    } else if is_null_sha(before) {
+        log::info!(
+            "Push created {}, scanning all history reachable from {}",
+            git_ref,
+            after
+        );
+        String::new()
+    } else if event_json["forced"].as_bool() == Some(true) {
+        match git::fetch_commit(&config.workspace_path, before).await {
+            Ok(()) => before.to_string(),
+            Err(e) => {
+                log::warn!(
+                    "Force push replaced {} which could not be fetched ({}), scanning all history reachable from {}",
+                    before,
+                    e,
+                    after
+                );
+                String::new()
+            }
+        }
+    } else {
+        before.to_string()
+    };
 
     Ok(EventContext {
         event_type: EventType::Push,
         repository,
         base_ref,
-        head_ref,
+        head_ref: after.to_string(),
         commits,
         pull_request: None,
+        inputs: None,
     })
 }
 
+/// Whether a SHA is the all-zero SHA GitHub sends for a missing commit
+pub(crate) fn is_null_sha(sha: &str) -> bool {
+    !sha.is_empty() && sha.bytes().all(|b| b == b'0')
+}
+
 /// Parse pull request event
 #[cfg(feature = "native")]
 async fn parse_pull_request_event(
@@ -206,6 +397,135 @@ async fn parse_pull_request_event(
     repository: Repository,
     config: &Config,
 ) -> Result<EventContext> {
+    let pull_request = parse_pull_request(event_json)?;
+    let pr_number = pull_request.number;
+    let base_sha = pull_request.base.sha.clone();
+    let head_sha = pull_request.head.sha.clone();
+    let pr_obj = &event_json["pull_request"];
+
+    // Fetch PR commits to determine exact scan range. The API lists at most
+    // 250 commits, so larger PRs are listed from the local clone instead.
+    let total_commits = pr_obj
+        .get("commits")
+        .and_then(|c| c.as_u64())
+        .map(|c| c as usize);
+
+    let pr_commits = if total_commits.is_some_and(|total| total > PR_COMMITS_API_LIMIT) {
+        list_commits_locally(config, &base_sha, &head_sha).await?
+    } else {
+        let client = crate::github::create_client(config).await?;
+        let commits =
+            crate::github::fetch_pr_commits(config, &client, &repository, pr_number).await?;
+        if total_commits.is_none() && commits.len() >= PR_COMMITS_API_LIMIT {
+            list_commits_locally(config, &base_sha, &head_sha).await?
+        } else {
+            commits
+        }
+    };
+
+    if pr_commits.is_empty() {
+        return Err(EventError::NoCommits.into());
+    }
+
+    let base_ref = config
+        .base_ref
+        .clone()
+        .unwrap_or_else(|| pr_commits[0].sha.clone());
+    let head_ref = pr_commits.last().unwrap().sha.clone();
+
+    Ok(EventContext {
+        event_type: EventType::PullRequest,
+        repository,
+        base_ref,
+        head_ref,
+        commits: pr_commits,
+        pull_request: Some(pull_request),
+        inputs: None,
+    })
+}
+
+/// Parse pull request target event
+///
+/// The scan range is taken from the base and head commits in the payload.
+/// The workflow has to check out the head commit (with enough history) for
+/// gitleaks to see it.
+fn parse_pull_request_target_event(
+    event_json: &serde_json::Value,
+    repository: Repository,
+) -> Result<EventContext> {
+    let pull_request = parse_pull_request(event_json)?;
+
+    Ok(EventContext {
+        event_type: EventType::PullRequestTarget,
+        repository,
+        base_ref: pull_request.base.sha.clone(),
+        head_ref: pull_request.head.sha.clone(),
+        commits: Vec::new(),
+        pull_request: Some(pull_request),
+        inputs: None,
+    })
+}
+
+/// Parse issue comment event
+///
+/// The payload does not describe the pull request's commits, so the pull
+/// request is fetched and its base and head commits are scanned as for
+/// `pull_request_target`.
+#[cfg(feature = "native")]
+async fn parse_issue_comment_event(
+    event_json: &serde_json::Value,
+    repository: Repository,
+    config: &Config,
+) -> Result<EventContext> {
+    let comment = parse_issue_comment(event_json)?;
+    if !comment.on_pull_request {
+        return Err(EventError::MissingField("issue.pull_request".to_string()).into());
+    }
+
+    let client = crate::github::create_client(config).await?;
+    let pr_json =
+        crate::github::fetch_pull_request(config, &client, &repository, comment.issue_number)
+            .await?;
+    let pull_request = parse_pull_request(&serde_json::json!({ "pull_request": pr_json }))?;
+
+    Ok(EventContext {
+        event_type: EventType::IssueComment,
+        repository,
+        base_ref: pull_request.base.sha.clone(),
+        head_ref: pull_request.head.sha.clone(),
+        commits: Vec::new(),
+        pull_request: Some(pull_request),
+        inputs: None,
+    })
+}
+
+/// Parse the comment of an issue comment event
+pub fn parse_issue_comment(event_json: &serde_json::Value) -> Result<IssueComment> {
+    let comment = &event_json["comment"];
+    let missing = |name: &str| EventError::MissingField(name.to_string());
+
+    Ok(IssueComment {
+        id: comment["id"]
+            .as_u64()
+            .ok_or_else(|| missing("comment.id"))?,
+        body: comment["body"]
+            .as_str()
+            .ok_or_else(|| missing("comment.body"))?
+            .to_string(),
+        author: comment["user"]["login"]
+            .as_str()
+            .ok_or_else(|| missing("comment.user.login"))?
+            .to_string(),
+        author_is_bot: comment["user"]["type"].as_str() == Some("Bot"),
+        issue_number: event_json["issue"]["number"]
+            .as_u64()
+            .ok_or_else(|| missing("issue.number"))?,
+        on_pull_request: event_json["issue"]["pull_request"].is_object(),
+    })
+}
+
+/// Parse the `pull_request` object of a pull request event
+fn parse_pull_request(event_json: &serde_json::Value) -> Result<PullRequest> {
     let pr_obj = event_json["pull_request"]
         .as_object()
         .ok_or_else(|| EventError::MissingField("pull_request".to_string()))?;
@@ -234,53 +554,208 @@ async fn parse_pull_request_event(
         .ok_or_else(|| EventError::MissingField("pull_request.head.ref".to_string()))?
         .to_string();
 
-    let pull_request = PullRequest {
+    let head_repo = pr_obj["head"]["repo"]["full_name"].as_str();
+    let base_repo = pr_obj["base"]["repo"]["full_name"].as_str();
+    let fork = head_repo.is_some() && head_repo != base_repo;
+
+    Ok(PullRequest {
         number: pr_number,
         base: GitReference {
-            sha: base_sha.clone(),
+            sha: base_sha,
             ref_name: base_ref_name,
         },
         head: GitReference {
-            sha: head_sha.clone(),
+            sha: head_sha,
             ref_name: head_ref_name,
         },
+        fork,
+    })
+}
+
+/// List PR commits from the local clone when the API cannot list them all
+#[cfg(feature = "native")]
+async fn list_commits_locally(
+    config: &Config,
+    base_sha: &str,
+    head_sha: &str,
+) -> Result<Vec<Commit>> {
+    log::warn!(
+        "Pull request exceeds the {} commit API limit, listing commits with local git",
+        PR_COMMITS_API_LIMIT
+    );
+
+    let history = git::History::Range {
+        base: base_sha.to_string(),
+        head: head_sha.to_string(),
     };
+    git::ensure_history(&config.workspace_path, &history).await?;
 
-    // Fetch PR commits to determine exact scan range
-    let pr_commits = crate::github::fetch_pr_commits(config, &repository, pr_number).await?;
+    let commits = git::list_commits(&config.workspace_path, base_sha, head_sha).await?;
+    log::info!("Listed {} commits from local git", commits.len());
 
-    if pr_commits.is_empty() {
-        return Err(EventError::NoCommits.into());
-    }
+    Ok(commits)
+}
 
-    let base_ref = config
-        .base_ref
-        .clone()
-        .unwrap_or_else(|| pr_commits[0].sha.clone());
-    let head_ref = pr_commits.last().unwrap().sha.clone();
+/// Parse merge group event
+///
+/// The merge queue builds a temporary branch on top of the target branch;
+/// everything it adds over `base_sha` is scanned.
+fn parse_merge_group_event(
+    event_json: &serde_json::Value,
+    repository: Repository,
+    config: &Config,
+) -> Result<EventContext> {
+    let group = event_json["merge_group"]
+        .as_object()
+        .ok_or_else(|| EventError::MissingField("merge_group".to_string()))?;
+
+    let field = |name: &str| {
+        group
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| EventError::MissingField(format!("merge_group.{}", name)))
+    };
+
+    let base_sha = field("base_sha")?;
+    let head_sha = field("head_sha")?;
+    let head_ref = field("head_ref")?;
+
+    log::info!("Merge group {} at {}", head_ref, head_sha);
 
     Ok(EventContext {
-        event_type: EventType::PullRequest,
+        event_type: EventType::MergeGroup,
         repository,
-        base_ref,
-        head_ref,
-        commits: pr_commits,
-        pull_request: Some(pull_request),
+        base_ref: config.base_ref.clone().unwrap_or(base_sha),
+        head_ref: head_sha,
+        commits: Vec::new(),
+        pull_request: None,
+        inputs: None,
     })
 }
 
 /// Parse workflow dispatch event
-fn parse_workflow_dispatch_event(repository: Repository) -> Result<EventContext> {
+///
+/// Without inputs the whole repository is scanned. The `base_ref`,
+/// `head_ref`, `since` and `paths` inputs narrow the scan, `report_format`
+/// writes an extra report and `fail_on` controls whether findings fail the
+/// workflow.
+fn parse_workflow_dispatch_event(
+    event_json: &serde_json::Value,
+    repository: Repository,
+) -> Result<EventContext> {
+    let payload = &event_json["inputs"];
+    // Inputs left blank in the Actions UI arrive as empty strings
+    let input = |name: &str| match &payload[name] {
+        serde_json::Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    };
+    let invalid = |name: &str, value: &str| EventError::InvalidInput {
+        name: name.to_string(),
+        value: value.to_string(),
+    };
+
+    let base_ref = input("base_ref").unwrap_or_default();
+    let head_ref = input("head_ref").unwrap_or_default();
+    for (name, git_ref) in [("base_ref", &base_ref), ("head_ref", &head_ref)] {
+        Config::validate_git_ref(git_ref)?;
+        if !is_scan_argument(git_ref) {
+            return Err(invalid(name, git_ref).into());
+        }
+    }
+
+    let since = input("since");
+    if let Some(since) = &since {
+        let valid = chrono::NaiveDate::parse_from_str(since, "%Y-%m-%d").is_ok()
+            || chrono::DateTime::parse_from_rfc3339(since).is_ok();
+        if !valid {
+            return Err(invalid("since", since).into());
+        }
+    }
+
+    let paths: Vec<String> = input("paths")
+        .unwrap_or_default()
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .collect();
+    for path in &paths {
+        Config::validate_git_ref(path)?;
+        if !is_scan_argument(path) {
+            return Err(invalid("paths", path).into());
+        }
+    }
+
+    let report_format = input("report_format");
+    if let Some(format) = &report_format {
+        crate::report::OutputFormat::from_str(format)?;
+    }
+
+    let fail_on = input("fail_on")
+        .map(|fail_on| fail_on.parse())
+        .transpose()?
+        .unwrap_or_default();
+
+    let inputs = DispatchInputs {
+        since,
+        paths,
+        report_format,
+        fail_on,
+    };
+    if inputs != DispatchInputs::default() || !base_ref.is_empty() || !head_ref.is_empty() {
+        log::info!("Scanning with workflow_dispatch inputs: {:?}", inputs);
+    }
+
     Ok(EventContext {
         event_type: EventType::WorkflowDispatch,
         repository,
-        base_ref: String::new(),
-        head_ref: String::new(),
+        base_ref,
+        head_ref,
         commits: Vec::new(),
         pull_request: None,
+        inputs: Some(inputs),
     })
 }
 
+/// Resolve the `base_ref` and `head_ref` inputs of a dispatch to commit SHAs
+///
+/// Branches other than the checked out one and tags are not in the clone, so
+/// they are fetched by name before the scan range is built from them.
+#[cfg(feature = "native")]
+async fn resolve_dispatch_refs(mut context: EventContext, config: &Config) -> Result<EventContext> {
+    for (name, git_ref) in [
+        ("base_ref", &mut context.base_ref),
+        ("head_ref", &mut context.head_ref),
+    ] {
+        if git_ref.is_empty() {
+            continue;
+        }
+        let sha = git::resolve_revision(&config.workspace_path, git_ref)
+            .await
+            .map_err(|e| {
+                EventError::HistoryUnavailable(format!(
+                    "{} input {} could not be resolved: {}",
+                    name, git_ref, e
+                ))
+            })?;
+        log::info!("Resolved {} input {} to {}", name, git_ref, sha);
+        *git_ref = sha;
+    }
+
+    Ok(context)
+}
+
+/// Whether an input can be passed to `git log` as a single argument
+///
+/// gitleaks splits its log options on spaces, and a leading dash would be
+/// read as an option.
+fn is_scan_argument(value: &str) -> bool {
+    !value.starts_with('-') && !value.chars().any(char::is_whitespace)
+}
+
 /// Parse schedule event
 fn parse_schedule_event(repository: Repository) -> Result<EventContext> {
     Ok(EventContext {
@@ -290,6 +765,7 @@ fn parse_schedule_event(repository: Repository) -> Result<EventContext> {
         head_ref: String::new(),
         commits: Vec::new(),
         pull_request: None,
+        inputs: None,
     })
 }
 
@@ -309,13 +785,16 @@ fn parse_commit(commit_json: &serde_json::Value) -> Option<Commit> {
 pub fn build_log_opts(context: &EventContext) -> String {
     match context.event_type {
         EventType::Push => {
-            if context.base_ref == context.head_ref {
+            if context.base_ref.is_empty() {
+                // New branch or tag: all history reachable from the head
+                format!("--no-merges --first-parent {}", context.head_ref)
+            } else if context.base_ref == context.head_ref {
                 // Single commit
                 "-1".to_string()
             } else {
-                // Range scan
+                // Range scan, excluding the commit the ref pointed at before
                 format!(
-                    "--no-merges --first-parent {}^..{}",
+                    "--no-merges --first-parent {}..{}",
                     context.base_ref, context.head_ref
                 )
             }
@@ -327,13 +806,60 @@ pub fn build_log_opts(context: &EventContext) -> String {
                 context.base_ref, context.head_ref
             )
         }
-        EventType::WorkflowDispatch | EventType::Schedule => {
+        EventType::PullRequestTarget | EventType::IssueComment => {
+            // The head commits are not on the base branch, so the base commit
+            // itself is excluded
+            format!(
+                "--no-merges --first-parent {}..{}",
+                context.base_ref, context.head_ref
+            )
+        }
+        EventType::MergeGroup => {
+            // Everything the queue adds on top of the target branch, including
+            // the commits of every pull request in the group
+            format!("{}..{}", context.base_ref, context.head_ref)
+        }
+        EventType::WorkflowDispatch => dispatch_log_opts(context),
+        EventType::Schedule => {
             // Full repository scan - no log-opts
             String::new()
         }
     }
 }
 
+/// Build log-opts for a workflow_dispatch scan, empty for a full scan
+///
+/// The range comes from the dispatch inputs; a context without inputs is
+/// always a full scan.
+fn dispatch_log_opts(context: &EventContext) -> String {
+    let Some(inputs) = &context.inputs else {
+        return String::new();
+    };
+    let mut opts = Vec::new();
+
+    if let Some(since) = &inputs.since {
+        opts.push(format!("--since={}", since));
+    }
+
+    match (context.base_ref.is_empty(), context.head_ref.is_empty()) {
+        (true, true) if !opts.is_empty() || !inputs.paths.is_empty() => {
+            // Keep the full scan's scope of every ref
+            opts.push("--full-history --all".to_string());
+        }
+        (true, true) => {}
+        (true, false) => opts.push(context.head_ref.clone()),
+        (false, true) => opts.push(format!("{}..HEAD", context.base_ref)),
+        (false, false) => opts.push(format!("{}..{}", context.base_ref, context.head_ref)),
+    }
+
+    if !inputs.paths.is_empty() {
+        opts.push("--".to_string());
+        opts.extend(inputs.paths.iter().cloned());
+    }
+
+    opts.join(" ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -353,6 +879,14 @@ mod tests {
             EventType::from_str("schedule").unwrap(),
             EventType::Schedule
         );
+        assert_eq!(
+            EventType::from_str("merge_group").unwrap(),
+            EventType::MergeGroup
+        );
+        assert_eq!(
+            EventType::from_str("pull_request_target").unwrap(),
+            EventType::PullRequestTarget
+        );
         assert!(EventType::from_str("invalid").is_err());
     }
 
@@ -370,11 +904,12 @@ mod tests {
             head_ref: "def456".to_string(),
             commits: Vec::new(),
             pull_request: None,
+            inputs: None,
         };
 
         assert_eq!(
             build_log_opts(&context),
-            "--no-merges --first-parent abc123^..def456"
+            "--no-merges --first-parent abc123..def456"
         );
 
         // Single commit
@@ -390,6 +925,7 @@ mod tests {
             head_ref: "abc123".to_string(),
             commits: Vec::new(),
             pull_request: None,
+            inputs: None,
         };
 
         assert_eq!(build_log_opts(&context), "-1");
@@ -407,8 +943,259 @@ mod tests {
             head_ref: String::new(),
             commits: Vec::new(),
             pull_request: None,
+            inputs: None,
         };
 
         assert_eq!(build_log_opts(&context), "");
     }
+
+    #[test]
+    fn test_parse_merge_group_event() {
+        let event = serde_json::json!({
+            "action": "checks_requested",
+            "merge_group": {
+                "head_sha": "def456",
+                "head_ref": "refs/heads/gh-readonly-queue/main/pr-42-abc123",
+                "base_sha": "abc123",
+                "base_ref": "refs/heads/main",
+            }
+        });
+        let repository = Repository {
+            owner: "owner".to_string(),
+            name: "repo".to_string(),
+            full_name: "owner/repo".to_string(),
+            html_url: "https://github.com/owner/repo".to_string(),
+        };
+        let config = Config::for_repository("owner/repo", std::path::PathBuf::from(".")).unwrap();
+
+        let context = parse_merge_group_event(&event, repository.clone(), &config).unwrap();
+        assert_eq!(context.event_type, EventType::MergeGroup);
+        assert_eq!(context.base_ref, "abc123");
+        assert_eq!(context.head_ref, "def456");
+        assert!(context.pull_request.is_none());
+        assert_eq!(build_log_opts(&context), "abc123..def456");
+
+        let event = serde_json::json!({ "merge_group": { "head_sha": "def456" } });
+        assert!(parse_merge_group_event(&event, repository, &config).is_err());
+    }
+
+    #[test]
+    fn test_parse_pull_request_target_event() {
+        let event = serde_json::json!({
+            "pull_request": {
+                "number": 7,
+                "base": {
+                    "ref": "main",
+                    "sha": "base123",
+                    "repo": { "full_name": "owner/repo" }
+                },
+                "head": {
+                    "ref": "patch-1",
+                    "sha": "head456",
+                    "repo": { "full_name": "contributor/repo" }
+                }
+            }
+        });
+        let repository = Repository {
+            owner: "owner".to_string(),
+            name: "repo".to_string(),
+            full_name: "owner/repo".to_string(),
+            html_url: "https://github.com/owner/repo".to_string(),
+        };
+
+        let mut context = parse_pull_request_target_event(&event, repository).unwrap();
+        assert_eq!(context.event_type, EventType::PullRequestTarget);
+        assert!(context.pull_request.as_ref().unwrap().fork);
+        assert!(context.is_pull_request());
+        assert!(!context.is_read_only());
+        assert_eq!(
+            build_log_opts(&context),
+            "--no-merges --first-parent base123..head456"
+        );
+
+        // Uploads name the PR head, not GITHUB_SHA and GITHUB_REF
+        let mut config =
+            Config::for_repository("owner/repo", std::path::PathBuf::from(".")).unwrap();
+        config.sha = "default789".to_string();
+        config.git_ref = "refs/heads/main".to_string();
+        let target = ("head456".to_string(), "refs/pull/7/head".to_string());
+        assert_eq!(context.code_scanning_target(&config), target);
+
+        // Comment commands resolve the same pull request head
+        context.event_type = EventType::IssueComment;
+        assert_eq!(context.code_scanning_target(&config), target);
+        assert_eq!(context.head_sha(&config), "head456");
+
+        // The same fork PR on `pull_request` only gets a read-only token
+        context.event_type = EventType::PullRequest;
+        assert!(context.is_read_only());
+    }
+
+    #[cfg(feature = "native")]
+    #[tokio::test]
+    async fn test_parse_push_event_ranges() {
+        let repository = Repository {
+            owner: "owner".to_string(),
+            name: "repo".to_string(),
+            full_name: "owner/repo".to_string(),
+            html_url: "https://github.com/owner/repo".to_string(),
+        };
+        let workspace = tempfile::TempDir::new().unwrap();
+        let config = Config::for_repository("owner/repo", workspace.path().to_path_buf()).unwrap();
+        let null_sha = "0".repeat(40);
+        let commit = serde_json::json!({
+            "id": "def456",
+            "message": "Update",
+            "author": { "name": "Jane", "email": "jane@example.com" }
+        });
+
+        // Existing branch: everything after the previous tip
+        let event = serde_json::json!({
+            "ref": "refs/heads/main",
+            "before": "abc123",
+            "after": "def456",
+            "commits": [commit],
+        });
+        let context = parse_push_event(&event, repository.clone(), &config)
+            .await
+            .unwrap();
+        assert_eq!(context.base_ref, "abc123");
+        assert_eq!(context.head_ref, "def456");
+        assert_eq!(
+            build_log_opts(&context),
+            "--no-merges --first-parent abc123..def456"
+        );
+
+        // New branch: all history reachable from the head
+        let event = serde_json::json!({
+            "ref": "refs/heads/feature",
+            "before": null_sha,
+            "after": "def456",
+            "created": true,
+            "commits": [commit],
+        });
+        let context = parse_push_event(&event, repository.clone(), &config)
+            .await
+            .unwrap();
+        assert_eq!(context.base_ref, "");
+        assert_eq!(
+            build_log_opts(&context),
+            "--no-merges --first-parent def456"
+        );
+
+        // New tag: the payload lists no commits
+        let event = serde_json::json!({
+            "ref": "refs/tags/v1.0.0",
+            "before": null_sha,
+            "after": "def456",
+            "created": true,
+            "commits": [],
+        });
+        let context = parse_push_event(&event, repository.clone(), &config)
+            .await
+            .unwrap();
+        assert!(context.commits.is_empty());
+        assert_eq!(context.head_ref, "def456");
+
+        // Force push whose previous tip is not in the clone
+        let event = serde_json::json!({
+            "ref": "refs/heads/main",
+            "before": "abc123",
+            "after": "def456",
+            "forced": true,
+            "commits": [commit],
+        });
+        let context = parse_push_event(&event, repository.clone(), &config)
+            .await
+            .unwrap();
+        assert_eq!(context.base_ref, "");
+
+        // Deleted branch: nothing to scan
+        let event = serde_json::json!({
+            "ref": "refs/heads/feature",
+            "before": "abc123",
+            "after": null_sha,
+            "deleted": true,
+            "commits": [],
+        });
+        let err = parse_push_event(&event, repository, &config)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::Error::Event(EventError::NoCommits)
+        ));
+    }
+
+    #[test]
+    fn test_parse_workflow_dispatch_inputs() {
+        let repository = Repository {
+            owner: "owner".to_string(),
+            name: "repo".to_string(),
+            full_name: "owner/repo".to_string(),
+            html_url: "https://github.com/owner/repo".to_string(),
+        };
+
+        // No inputs: full scan
+        let event = serde_json::json!({ "ref": "refs/heads/main", "inputs": {} });
+        let context = parse_workflow_dispatch_event(&event, repository.clone()).unwrap();
+        assert_eq!(context.inputs, Some(DispatchInputs::default()));
+        assert_eq!(build_log_opts(&context), "");
+
+        // Without a head_ref input the workflow's commit was scanned
+        let mut config =
+            Config::for_repository("owner/repo", std::path::PathBuf::from(".")).unwrap();
+        config.sha = "default789".to_string();
+        assert_eq!(context.head_sha(&config), "default789");
+
+        let event = serde_json::json!({
+            "inputs": {
+                "base_ref": "v1.0.0",
+                "head_ref": "main",
+                "since": "2025-01-31",
+                "paths": "src/config, deploy\n",
+                "report_format": "markdown",
+                "fail_on": "error",
+            }
+        });
+        let context = parse_workflow_dispatch_event(&event, repository.clone()).unwrap();
+        assert_eq!(context.base_ref, "v1.0.0");
+        assert_eq!(context.head_ref, "main");
+        // Checks go on the head input, which is resolved to a SHA before the scan
+        assert_eq!(context.head_sha(&config), "main");
+        let inputs = context.inputs.as_ref().unwrap();
+        assert_eq!(inputs.paths, ["src/config", "deploy"]);
+        assert_eq!(inputs.report_format.as_deref(), Some("markdown"));
+        assert_eq!(inputs.fail_on, FailOn::Error);
+        assert_eq!(
+            build_log_opts(&context),
+            "--since=2025-01-31 v1.0.0..main -- src/config deploy"
+        );
+
+        // A path alone keeps the full scan's scope
+        let event = serde_json::json!({ "inputs": { "paths": "infra", "base_ref": "" } });
+        let context = parse_workflow_dispatch_event(&event, repository.clone()).unwrap();
+        assert_eq!(build_log_opts(&context), "--full-history --all -- infra");
+
+        let event = serde_json::json!({ "inputs": { "base_ref": "abc123" } });
+        let context = parse_workflow_dispatch_event(&event, repository.clone()).unwrap();
+        assert_eq!(build_log_opts(&context), "abc123..HEAD");
+
+        for inputs in [
+            serde_json::json!({ "base_ref": "main; whoami" }),
+            serde_json::json!({ "head_ref": "--output=/tmp/x" }),
+            serde_json::json!({ "since": "last week" }),
+            serde_json::json!({ "paths": "../outside" }),
+            serde_json::json!({ "paths": "my dir" }),
+            serde_json::json!({ "report_format": "pdf" }),
+            serde_json::json!({ "fail_on": "warnings" }),
+        ] {
+            let event = serde_json::json!({ "inputs": inputs });
+            assert!(
+                parse_workflow_dispatch_event(&event, repository.clone()).is_err(),
+                "accepted {}",
+                inputs
+            );
+        }
+    }
 }
@@ -0,0 +1,181 @@
+//! GitHub App authentication
+//!
+//! An app authenticates by signing a short-lived JWT with its private key and
+//! exchanging it for an installation token. Installation tokens expire after
+//! an hour, so they are cached per app and repository and refreshed shortly
+//! before they expire.
+
+use super::client::GitHubClient;
+use crate::config::{Config, GitHubApp};
+use crate::error::{Error, GitHubError, Result};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tokio::sync::Mutex;
+
+/// Lifetime of the app JWT (GitHub allows at most 10 minutes)
+const JWT_LIFETIME: Duration = Duration::minutes(9);
+
+/// Backdating of the JWT issue time to allow for clock drift
+const JWT_CLOCK_SKEW: Duration = Duration::seconds(60);
+
+/// Installation tokens this close to expiry are replaced
+const REFRESH_MARGIN: Duration = Duration::minutes(5);
+
+/// Installation token and its expiry
+#[derive(Debug, Clone)]
+pub struct InstallationToken {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl InstallationToken {
+    /// Whether the token should be replaced before its next use
+    pub fn needs_refresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at - now <= REFRESH_MARGIN
+    }
+}
+
+#[derive(Serialize)]
+struct Claims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+}
+
+/// Cached installation tokens, keyed by app id and repository
+fn token_cache() -> &'static Mutex<HashMap<String, InstallationToken>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, InstallationToken>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Get an installation token for the configured repository
+///
+/// Reuses the cached token until it is about to expire.
+pub async fn installation_token(config: &Config, app: &GitHubApp) -> Result<String> {
+    let key = format!("{}:{}", app.app_id, config.repository);
+    let mut cache = token_cache().lock().await;
+
+    if let Some(token) = cache.get(&key) {
+        if !token.needs_refresh(Utc::now()) {
+            return Ok(token.token.clone());
+        }
+        log::debug!("GitHub App installation token expires soon, refreshing");
+    }
+
+    let token = fetch_installation_token(&config.github_api_url, app, &config.repository).await?;
+    let value = token.token.clone();
+    cache.insert(key, token);
+
+    Ok(value)
+}
+
+/// Exchange an app JWT for an installation token scoped to `repository`
+pub async fn fetch_installation_token(
+    api_url: &str,
+    app: &GitHubApp,
+    repository: &str,
+) -> Result<InstallationToken> {
+    let jwt = create_jwt(app, Utc::now())?;
+    let client = GitHubClient::new(&jwt)?;
+
+    let installation_id = match app.installation_id {
+        Some(id) => id,
+        None => {
+            let url = format!("{}/repos/{}/installation", api_url, repository);
+            let installation = client.get(&url).await.map_err(|e| match e {
+                Error::GitHub(GitHubError::NotFound(_)) => {
+                    Error::GitHub(GitHubError::AuthenticationFailed(format!(
+                        "GitHub App {} is not installed on {}",
+                        app.app_id, repository
+                    )))
+                }
+                e => e,
+            })?;
+            installation["id"].as_u64().ok_or_else(|| {
+                GitHubError::ParseError("installation response has no id".to_string())
+            })?
+        }
+    };
+
+    log::info!(
+        "Requesting installation token for GitHub App {} (installation {})",
+        app.app_id,
+        installation_id
+    );
+
+    let name = repository.split('/').nth(1).unwrap_or(repository);
+    let url = format!(
+        "{}/app/installations/{}/access_tokens",
+        api_url, installation_id
+    );
+    let response = client
+        .post(&url, &serde_json::json!({ "repositories": [name] }))
+        .await?;
+
+    let token = response["token"]
+        .as_str()
+        .ok_or_else(|| GitHubError::ParseError("access token response has no token".to_string()))?
+        .to_string();
+
+    let expires_at = response["expires_at"]
+        .as_str()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|t| t.with_timezone(&Utc))
+        .ok_or_else(|| {
+            GitHubError::ParseError("access token response has no valid expires_at".to_string())
+        })?;
+
+    Ok(InstallationToken { token, expires_at })
+}
+
+/// Sign the JWT that authenticates as the app itself
+pub fn create_jwt(app: &GitHubApp, now: DateTime<Utc>) -> Result<String> {
+    let key = EncodingKey::from_rsa_pem(app.private_key.as_bytes()).map_err(|e| {
+        GitHubError::AuthenticationFailed(format!("invalid GitHub App private key: {}", e))
+    })?;
+
+    let claims = Claims {
+        iat: (now - JWT_CLOCK_SKEW).timestamp(),
+        exp: (now + JWT_LIFETIME).timestamp(),
+        iss: app.app_id.clone(),
+    };
+
+    jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &key).map_err(|e| {
+        GitHubError::AuthenticationFailed(format!("failed to sign GitHub App JWT: {}", e)).into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_refresh() {
+        let now = Utc::now();
+        let token = InstallationToken {
+            token: "ghs_test".to_string(),
+            expires_at: now + Duration::minutes(60),
+        };
+        assert!(!token.needs_refresh(now));
+        assert!(token.needs_refresh(now + Duration::minutes(56)));
+        assert!(token.needs_refresh(now + Duration::minutes(61)));
+    }
+
+    #[test]
+    fn test_create_jwt_rejects_invalid_key() {
+        let app = GitHubApp {
+            app_id: "12345".to_string(),
+            private_key: "not a key".to_string(),
+            installation_id: None,
+        };
+
+        let err = create_jwt(&app, Utc::now()).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::GitHub(GitHubError::AuthenticationFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,506 @@
+//! GitHub HTTP client
+//!
+//! A thin reqwest wrapper that classifies failures by HTTP status code and
+//! retries only what is safe to retry. Rate limited responses (429, or 403
+//! with an exhausted quota or a secondary limit) wait for `Retry-After` or
+//! `X-RateLimit-Reset`; server errors and network failures are retried with
+//! jittered exponential backoff, but only for idempotent requests.
+
+use crate::error::{GitHubError, Result};
+use reqwest::header::{HeaderMap, ACCEPT, AUTHORIZATION, LINK, USER_AGENT};
+use reqwest::{Method, StatusCode};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Items requested per page from list endpoints (the API maximum)
+const PER_PAGE: u32 = 100;
+
+/// REST API version sent with every request
+const API_VERSION: &str = "2022-11-28";
+
+/// Longest a single request may take, including reading the response body
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Longest to wait for a connection to the API
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Retry behaviour of the client
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total attempts per request, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each further retry
+    pub base_delay: Duration,
+    /// Longest the client will wait before a retry. Rate limits that reset
+    /// later than this fail with `RateLimitExceeded` instead of blocking.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Why a request failed, decided from the response status and headers
+#[derive(Debug)]
+enum Failure {
+    /// Rate limited; retry after the given delay (if known)
+    RateLimited(Option<Duration>),
+    /// Transient server or network failure
+    Transient(GitHubError),
+    /// Permanent failure that retrying will not fix
+    Permanent(GitHubError),
+}
+
+/// Authenticated client for the GitHub REST and GraphQL APIs
+#[derive(Debug, Clone)]
+pub struct GitHubClient {
+    http: reqwest::Client,
+    token: String,
+    retry: RetryPolicy,
+    dry_run: bool,
+}
+
+impl GitHubClient {
+    /// Create a client authenticating with `token` (may be empty)
+    pub fn new(token: &str) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .map_err(|e| GitHubError::NetworkError(e.to_string()))?;
+
+        Ok(Self {
+            http,
+            token: token.to_string(),
+            retry: RetryPolicy::default(),
+            dry_run: false,
+        })
+    }
+
+    /// Replace the retry policy
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Print writes instead of sending them; reads are still sent
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// GET a JSON resource
+    pub async fn get(&self, url: &str) -> Result<serde_json::Value> {
+        Ok(self.send(Method::GET, url, None).await?.0)
+    }
+
+    /// GET every page of a list endpoint by following `Link` headers
+    pub async fn get_all_pages(&self, url: &str) -> Result<Vec<serde_json::Value>> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let mut next = Some(format!("{}{}per_page={}", url, separator, PER_PAGE));
+        let mut items = Vec::new();
+
+        while let Some(url) = next {
+            let (body, headers) = self.send(Method::GET, &url, None).await?;
+
+            match body {
+                serde_json::Value::Array(page) => items.extend(page),
+                _ => {
+                    return Err(GitHubError::ParseError(format!(
+                        "expected a JSON array from {}",
+                        url
+                    ))
+                    .into())
+                }
+            }
+
+            next = headers
+                .get(LINK)
+                .and_then(|link| link.to_str().ok())
+                .and_then(next_link);
+        }
+
+        Ok(items)
+    }
+
+    /// POST a JSON body (not retried unless rate limited)
+    pub async fn post(&self, url: &str, body: &serde_json::Value) -> Result<serde_json::Value> {
+        if self.dry_run {
+            return Ok(dry_run_write(&Method::POST, url, body));
+        }
+        Ok(self.send(Method::POST, url, Some(body)).await?.0)
+    }
+
+    /// PATCH a JSON body
+    pub async fn patch(&self, url: &str, body: &serde_json::Value) -> Result<serde_json::Value> {
+        if self.dry_run {
+            return Ok(dry_run_write(&Method::PATCH, url, body));
+        }
+        Ok(self.send(Method::PATCH, url, Some(body)).await?.0)
+    }
+
+    /// Run a GraphQL query or mutation and return its `data`
+    ///
+    /// GraphQL errors are reported with status 200, as GitHub returns them.
+    pub async fn graphql(
+        &self,
+        url: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let body = serde_json::json!({ "query": query, "variables": variables });
+        if self.dry_run && query.trim_start().starts_with("mutation") {
+            dry_run_write(&Method::POST, url, &body);
+            return Ok(serde_json::Value::Null);
+        }
+        let (response, _) = self.send(Method::POST, url, Some(&body)).await?;
+
+        if let Some(errors) = response["errors"].as_array() {
+            let messages: Vec<&str> = errors
+                .iter()
+                .filter_map(|e| e["message"].as_str())
+                .collect();
+            return Err(GitHubError::RequestFailed {
+                status: 200,
+                message: format!("GraphQL error: {}", messages.join("; ")),
+            }
+            .into());
+        }
+
+        Ok(response["data"].clone())
+    }
+
+    /// Send a request, retrying according to the policy
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<(serde_json::Value, HeaderMap)> {
+        let idempotent = method != Method::POST;
+        let mut attempt = 1;
+
+        loop {
+            let failure = match self.send_once(method.clone(), url, body).await {
+                Ok(response) => return Ok(response),
+                Err(failure) => failure,
+            };
+
+            let can_retry = attempt < self.retry.max_attempts;
+            let delay = match failure {
+                Failure::RateLimited(wait) if can_retry => {
+                    let wait = wait.unwrap_or_else(|| self.backoff(attempt));
+                    if wait > self.retry.max_delay {
+                        log::warn!(
+                            "Rate limit resets in {:?}, longer than the {:?} retry limit",
+                            wait,
+                            self.retry.max_delay
+                        );
+                        return Err(GitHubError::RateLimitExceeded.into());
+                    }
+                    wait
+                }
+                Failure::RateLimited(_) => return Err(GitHubError::RateLimitExceeded.into()),
+                Failure::Transient(_) if can_retry && idempotent => self.backoff(attempt),
+                Failure::Transient(error) | Failure::Permanent(error) => return Err(error.into()),
+            };
+
+            log::warn!(
+                "{} {} failed (attempt {}/{}), retrying in {:?}",
+                method,
+                url,
+                attempt,
+                self.retry.max_attempts,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Send a single request and classify any failure
+    async fn send_once(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<&serde_json::Value>,
+    ) -> std::result::Result<(serde_json::Value, HeaderMap), Failure> {
+        let mut request = self
+            .http
+            .request(method, url)
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(
+                USER_AGENT,
+                concat!("SecretScout/", env!("CARGO_PKG_VERSION")),
+            )
+            .header("X-GitHub-Api-Version", API_VERSION);
+
+        if !self.token.is_empty() {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", self.token));
+        }
+        if let Some(body) = body {
+            request = request
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.to_string());
+        }
+
+        let response = request.send().await.map_err(|e| {
+            let error = if e.is_timeout() {
+                GitHubError::Timeout(e.to_string())
+            } else {
+                GitHubError::NetworkError(e.to_string())
+            };
+            Failure::Transient(error)
+        })?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| Failure::Transient(GitHubError::NetworkError(e.to_string())))?;
+
+        if status.is_success() {
+            let body = if text.trim().is_empty() {
+                serde_json::Value::Null
+            } else {
+                serde_json::from_str(&text)
+                    .map_err(|e| Failure::Permanent(GitHubError::ParseError(e.to_string())))?
+            };
+            return Ok((body, headers));
+        }
+
+        Err(classify(status, &headers, &text))
+    }
+
+    /// Exponential backoff with up to 50% jitter
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self.retry.base_delay * 2_u32.saturating_pow(attempt - 1);
+        let jitter = delay.mul_f64(fastrand::f64() * 0.5);
+        (delay + jitter).min(self.retry.max_delay)
+    }
+}
+
+/// Classify a failed response by status code and rate limit headers
+fn classify(status: StatusCode, headers: &HeaderMap, body: &str) -> Failure {
+    let message = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|json| json["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| body.to_string());
+
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    let retry_after = header("retry-after").map(Duration::from_secs);
+    let quota_exhausted = header("x-ratelimit-remaining") == Some(0);
+    let secondary_limit = message
+        .to_ascii_lowercase()
+        .contains("secondary rate limit");
+
+    if status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::FORBIDDEN
+            && (quota_exhausted || secondary_limit || retry_after.is_some()))
+    {
+        let until_reset = header("x-ratelimit-reset").map(|reset| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            // Add a second so the retry lands after the reset
+            Duration::from_secs(reset.saturating_sub(now) + 1)
+        });
+        return Failure::RateLimited(retry_after.or(if quota_exhausted {
+            until_reset
+        } else {
+            None
+        }));
+    }
+
+    let message = format!("{} ({})", message, status);
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Failure::Permanent(GitHubError::AuthenticationFailed(message))
+        }
+        StatusCode::NOT_FOUND => Failure::Permanent(GitHubError::NotFound(message)),
+        StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => {
+            Failure::Transient(GitHubError::Timeout(message))
+        }
+        s if s.is_server_error() => Failure::Transient(GitHubError::RequestFailed {
+            status: s.as_u16(),
+            message,
+        }),
+        s => Failure::Permanent(GitHubError::RequestFailed {
+            status: s.as_u16(),
+            message,
+        }),
+    }
+}
+
+/// Print a write that a dry run skips and return a stand-in response
+///
+/// The stand-in carries the `id` and `number` fields callers read back from
+/// created check runs, issues and comments.
+fn dry_run_write(method: &Method, url: &str, body: &serde_json::Value) -> serde_json::Value {
+    println!("{}", format_dry_run_write(method, url, body));
+    serde_json::json!({ "id": 0, "number": 0 })
+}
+
+/// Render a skipped write, printing a Markdown `body` as-is so it reads as posted
+fn format_dry_run_write(method: &Method, url: &str, body: &serde_json::Value) -> String {
+    let mut out = format!("[dry-run] {} {}", method, url);
+    let mut fields = body.clone();
+    let text = fields
+        .as_object_mut()
+        .and_then(|map| map.remove("body"))
+        .and_then(|b| b.as_str().map(str::to_string));
+
+    if fields.as_object().is_none_or(|map| !map.is_empty()) {
+        out.push('\n');
+        out.push_str(&serde_json::to_string_pretty(&fields).unwrap_or_default());
+    }
+    if let Some(text) = text {
+        out.push('\n');
+        out.push_str(&text);
+    }
+    out
+}
+
+/// Extract the `rel="next"` URL from a `Link` header
+fn next_link(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|p| p.trim() == "rel=\"next\"")
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_next_link() {
+        let link = "<https://api.github.com/repos/o/r/pulls?page=2>; rel=\"next\", \
+                    <https://api.github.com/repos/o/r/pulls?page=5>; rel=\"last\"";
+        assert_eq!(
+            next_link(link).as_deref(),
+            Some("https://api.github.com/repos/o/r/pulls?page=2")
+        );
+        assert_eq!(
+            next_link("<https://api.github.com/x?page=1>; rel=\"prev\""),
+            None
+        );
+    }
+
+    #[test]
+    fn test_classify_rate_limits() {
+        let secondary = classify(
+            StatusCode::FORBIDDEN,
+            &headers(&[("retry-after", "30")]),
+            r#"{"message":"You have exceeded a secondary rate limit."}"#,
+        );
+        assert!(matches!(
+            secondary,
+            Failure::RateLimited(Some(d)) if d == Duration::from_secs(30)
+        ));
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let primary = classify(
+            StatusCode::FORBIDDEN,
+            &headers(&[
+                ("x-ratelimit-remaining", "0"),
+                ("x-ratelimit-reset", &(now + 10).to_string()),
+            ]),
+            r#"{"message":"API rate limit exceeded"}"#,
+        );
+        assert!(matches!(
+            primary,
+            Failure::RateLimited(Some(d)) if d >= Duration::from_secs(9) && d <= Duration::from_secs(11)
+        ));
+
+        assert!(matches!(
+            classify(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new(), ""),
+            Failure::RateLimited(None)
+        ));
+    }
+
+    #[test]
+    fn test_classify_errors() {
+        assert!(matches!(
+            classify(
+                StatusCode::FORBIDDEN,
+                &headers(&[("x-ratelimit-remaining", "4999")]),
+                r#"{"message":"Resource not accessible by integration"}"#
+            ),
+            Failure::Permanent(GitHubError::AuthenticationFailed(m)) if m.contains("not accessible")
+        ));
+        assert!(matches!(
+            classify(StatusCode::NOT_FOUND, &HeaderMap::new(), ""),
+            Failure::Permanent(GitHubError::NotFound(_))
+        ));
+        assert!(matches!(
+            classify(StatusCode::UNPROCESSABLE_ENTITY, &HeaderMap::new(), ""),
+            Failure::Permanent(GitHubError::RequestFailed { status: 422, .. })
+        ));
+        assert!(matches!(
+            classify(StatusCode::BAD_GATEWAY, &HeaderMap::new(), ""),
+            Failure::Transient(GitHubError::RequestFailed { status: 502, .. })
+        ));
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let client = GitHubClient::new("")
+            .unwrap()
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 10,
+                base_delay: Duration::from_secs(1),
+                max_delay: Duration::from_secs(5),
+            });
+
+        let first = client.backoff(1);
+        assert!(first >= Duration::from_secs(1) && first <= Duration::from_millis(1500));
+        assert_eq!(client.backoff(8), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_format_dry_run_write() {
+        let comment = serde_json::json!({ "body": "## Findings\n\n- one" });
+        assert_eq!(
+            format_dry_run_write(&Method::POST, "https://api/comments", &comment),
+            "[dry-run] POST https://api/comments\n## Findings\n\n- one"
+        );
+
+        let status = serde_json::json!({ "state": "failure", "context": "secretscout" });
+        let printed = format_dry_run_write(&Method::PATCH, "https://api/statuses", &status);
+        assert!(printed.starts_with("[dry-run] PATCH https://api/statuses\n{"));
+        assert!(printed.contains("\"state\": \"failure\""));
+    }
+}
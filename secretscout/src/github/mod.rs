@@ -1,16 +1,20 @@
 //! GitHub API client module
 //!
-//! This module provides GitHub API integration using octocrab with retry logic,
-//! rate limit handling, and all necessary API operations.
+//! This module provides all GitHub API operations SecretScout needs. Requests
+//! go through [`client::GitHubClient`], which handles retries and rate limits.
+
+#[cfg(feature = "native")]
+pub mod app;
+#[cfg(feature = "native")]
+pub mod client;
 
 use crate::config::Config;
-use crate::error::{GitHubError, Result};
+use crate::error::{Error, GitHubError, Result};
 use crate::events::{Author, Commit, Repository};
 
 #[cfg(feature = "native")]
-use octocrab::Octocrab;
+use client::GitHubClient;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
 
 /// PR review comment for posting
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +26,23 @@ pub struct PRComment {
     pub side: String, // "RIGHT" or "LEFT"
 }
 
+/// Event submitted with a pull request review
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewEvent {
+    Comment,
+    RequestChanges,
+}
+
+/// Pull request review thread as reported by GraphQL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewThread {
+    pub id: String,
+    pub is_resolved: bool,
+    /// REST ids of the comments in the thread
+    pub comment_ids: Vec<u64>,
+}
+
 /// Account type (User vs Organization)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -37,42 +58,82 @@ pub struct AccountInfo {
     pub login: String,
 }
 
-/// Create an octocrab instance with authentication
+/// Processing state of a SARIF upload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SarifProcessingStatus {
+    Pending,
+    Complete,
+    Failed,
+}
+
+/// Status of a SARIF upload as reported by code scanning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifUploadStatus {
+    pub processing_status: SarifProcessingStatus,
+    #[serde(default)]
+    pub analyses_url: Option<String>,
+    #[serde(default)]
+    pub errors: Option<Vec<String>>,
+}
+
+/// State of a commit status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitState {
+    Pending,
+    Success,
+    Failure,
+    Error,
+}
+
+/// Commit status shown next to a commit and in pull request checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitStatus {
+    pub state: CommitState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_url: Option<String>,
+    pub description: String,
+    pub context: String,
+}
+
+/// Check run annotation for a single finding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckAnnotation {
+    pub path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub annotation_level: String, // "notice", "warning" or "failure"
+    pub title: String,
+    pub message: String,
+}
+
+/// Create a client authenticated as the GitHub App or with the token
 #[cfg(feature = "native")]
-fn create_client(token: &str) -> Result<Octocrab> {
-    Octocrab::builder()
-        .personal_token(token.to_string())
-        .build()
-        .map_err(|e| GitHubError::NetworkError(e.to_string()).into())
+pub async fn create_client(config: &Config) -> Result<GitHubClient> {
+    let client = match &config.github_app {
+        Some(github_app) => GitHubClient::new(&app::installation_token(config, github_app).await?)?,
+        None => GitHubClient::new(&config.github_token)?,
+    };
+    Ok(client.with_dry_run(config.dry_run))
 }
 
 /// Fetch PR commits with retry
 #[cfg(feature = "native")]
 pub async fn fetch_pr_commits(
     config: &Config,
+    client: &GitHubClient,
     repository: &Repository,
     pr_number: i64,
 ) -> Result<Vec<Commit>> {
     log::info!("Fetching commits for PR #{}", pr_number);
 
-    let octocrab = create_client(&config.github_token)?;
-
-    // Use octocrab HTTP client to fetch commits directly
     let url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}/commits",
-        repository.owner, repository.name, pr_number
+        "{}/repos/{}/{}/pulls/{}/commits",
+        config.github_api_url, repository.owner, repository.name, pr_number
     );
 
-    let response = retry_with_backoff(|| async {
-        octocrab
-            .get::<Vec<serde_json::Value>, _, ()>(&url, None::<&()>)
-            .await
-            .map_err(|e| GitHubError::RequestFailed {
-                status: 0,
-                message: e.to_string(),
-            })
-    })
-    .await?;
+    let response = client.get_all_pages(&url).await?;
 
     let result: Vec<Commit> = response
         .into_iter()
@@ -99,32 +160,38 @@ pub async fn fetch_pr_commits(
     Ok(result)
 }
 
+/// Fetch a pull request
+#[cfg(feature = "native")]
+pub async fn fetch_pull_request(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    pr_number: u64,
+) -> Result<serde_json::Value> {
+    let url = format!(
+        "{}/repos/{}/{}/pulls/{}",
+        config.github_api_url, repository.owner, repository.name, pr_number
+    );
+
+    client.get(&url).await
+}
+
 /// Fetch existing PR review comments
 #[cfg(feature = "native")]
 pub async fn fetch_pr_comments(
     config: &Config,
+    client: &GitHubClient,
     repository: &Repository,
     pr_number: i64,
 ) -> Result<Vec<serde_json::Value>> {
     log::debug!("Fetching existing PR comments for deduplication");
 
-    let octocrab = create_client(&config.github_token)?;
-
     let url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}/comments",
-        repository.owner, repository.name, pr_number
+        "{}/repos/{}/{}/pulls/{}/comments",
+        config.github_api_url, repository.owner, repository.name, pr_number
     );
 
-    let result: Vec<serde_json::Value> = retry_with_backoff(|| async {
-        octocrab
-            .get::<Vec<serde_json::Value>, _, ()>(&url, None::<&()>)
-            .await
-            .map_err(|e| GitHubError::RequestFailed {
-                status: 0,
-                message: e.to_string(),
-            })
-    })
-    .await?;
+    let result = client.get_all_pages(&url).await?;
 
     log::debug!("Fetched {} existing comments", result.len());
 
@@ -135,17 +202,16 @@ pub async fn fetch_pr_comments(
 #[cfg(feature = "native")]
 pub async fn post_pr_comment(
     config: &Config,
+    client: &GitHubClient,
     repository: &Repository,
     pr_number: i64,
     comment: &PRComment,
 ) -> Result<()> {
     log::debug!("Posting comment on {}:{}", comment.path, comment.line);
 
-    let octocrab = create_client(&config.github_token)?;
-
     let url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}/comments",
-        repository.owner, repository.name, pr_number
+        "{}/repos/{}/{}/pulls/{}/comments",
+        config.github_api_url, repository.owner, repository.name, pr_number
     );
 
     let body = serde_json::json!({
@@ -156,51 +222,225 @@ pub async fn post_pr_comment(
         "side": comment.side,
     });
 
-    retry_with_backoff::<_, _, (), _>(|| async {
-        octocrab
-            .post::<serde_json::Value, _>(&url, Some(&body))
-            .await
-            .map_err(|e| {
-                // Check for specific error types
-                let error_msg = e.to_string();
-                if error_msg.contains("422") || error_msg.contains("Unprocessable") {
-                    GitHubError::DiffTooLarge
-                } else if error_msg.contains("401") || error_msg.contains("403") {
-                    GitHubError::AuthenticationFailed(error_msg)
-                } else if error_msg.contains("404") {
-                    GitHubError::NotFound(error_msg)
-                } else {
-                    GitHubError::RequestFailed {
-                        status: 0,
-                        message: error_msg,
-                    }
-                }
+    client.post(&url, &body).await.map_err(|e| match e {
+        // GitHub rejects comments on lines outside the diff
+        Error::GitHub(GitHubError::RequestFailed { status: 422, .. }) => {
+            GitHubError::DiffTooLarge.into()
+        }
+        e => e,
+    })?;
+
+    Ok(())
+}
+
+/// Submit a pull request review with all line comments attached
+///
+/// GitHub validates the whole batch at once, so a single comment on a line
+/// outside the diff rejects the review.
+#[cfg(feature = "native")]
+#[allow(clippy::too_many_arguments)]
+pub async fn submit_pr_review(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    pr_number: i64,
+    commit_id: &str,
+    body: &str,
+    event: ReviewEvent,
+    comments: &[PRComment],
+) -> Result<()> {
+    log::debug!(
+        "Submitting review with {} comments on PR #{}",
+        comments.len(),
+        pr_number
+    );
+
+    let url = format!(
+        "{}/repos/{}/{}/pulls/{}/reviews",
+        config.github_api_url, repository.owner, repository.name, pr_number
+    );
+
+    let review_comments: Vec<serde_json::Value> = comments
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "path": c.path,
+                "line": c.line,
+                "side": c.side,
+                "body": c.body,
             })
-    })
+        })
+        .collect();
+
+    let body = serde_json::json!({
+        "commit_id": commit_id,
+        "body": body,
+        "event": event,
+        "comments": review_comments,
+    });
+
+    client.post(&url, &body).await?;
+
+    Ok(())
+}
+
+/// Reply to a PR review comment
+#[cfg(feature = "native")]
+pub async fn reply_to_pr_comment(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    pr_number: i64,
+    comment_id: u64,
+    body: &str,
+) -> Result<()> {
+    let url = format!(
+        "{}/repos/{}/{}/pulls/{}/comments/{}/replies",
+        config.github_api_url, repository.owner, repository.name, pr_number, comment_id
+    );
+
+    let body = serde_json::json!({ "body": body });
+
+    client.post(&url, &body).await?;
+
+    Ok(())
+}
+
+/// Fetch all review threads of a pull request
+#[cfg(feature = "native")]
+pub async fn fetch_review_threads(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    pr_number: i64,
+) -> Result<Vec<ReviewThread>> {
+    const QUERY: &str = "query($owner: String!, $name: String!, $number: Int!, $after: String) {
+        repository(owner: $owner, name: $name) {
+            pullRequest(number: $number) {
+                reviewThreads(first: 100, after: $after) {
+                    pageInfo { hasNextPage endCursor }
+                    nodes { id isResolved comments(first: 100) { nodes { databaseId } } }
+                }
+            }
+        }
+    }";
+
+    let mut threads = Vec::new();
+    let mut after: Option<String> = None;
+
+    loop {
+        let data = graphql(
+            config,
+            client,
+            QUERY,
+            serde_json::json!({
+                "owner": repository.owner,
+                "name": repository.name,
+                "number": pr_number,
+                "after": after,
+            }),
+        )
+        .await?;
+
+        let page = &data["repository"]["pullRequest"]["reviewThreads"];
+        let nodes = page["nodes"].as_array().ok_or_else(|| {
+            GitHubError::ParseError("Review threads response has no nodes".to_string())
+        })?;
+
+        for node in nodes {
+            threads.push(ReviewThread {
+                id: node["id"].as_str().unwrap_or_default().to_string(),
+                is_resolved: node["isResolved"].as_bool().unwrap_or(false),
+                comment_ids: node["comments"]["nodes"]
+                    .as_array()
+                    .map(|comments| {
+                        comments
+                            .iter()
+                            .filter_map(|c| c["databaseId"].as_u64())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            });
+        }
+
+        match page["pageInfo"]["endCursor"].as_str() {
+            Some(cursor) if page["pageInfo"]["hasNextPage"].as_bool() == Some(true) => {
+                after = Some(cursor.to_string());
+            }
+            _ => break,
+        }
+    }
+
+    Ok(threads)
+}
+
+/// Resolve a pull request review thread
+#[cfg(feature = "native")]
+pub async fn resolve_review_thread(
+    config: &Config,
+    client: &GitHubClient,
+    thread_id: &str,
+) -> Result<()> {
+    const MUTATION: &str = "mutation($threadId: ID!) {
+        resolveReviewThread(input: { threadId: $threadId }) { thread { id } }
+    }";
+
+    graphql(
+        config,
+        client,
+        MUTATION,
+        serde_json::json!({ "threadId": thread_id }),
+    )
     .await?;
 
     Ok(())
 }
 
+/// Minimize (hide) a comment as outdated
+#[cfg(feature = "native")]
+pub async fn minimize_comment(config: &Config, client: &GitHubClient, node_id: &str) -> Result<()> {
+    const MUTATION: &str = "mutation($subjectId: ID!) {
+        minimizeComment(input: { subjectId: $subjectId, classifier: OUTDATED }) {
+            minimizedComment { isMinimized }
+        }
+    }";
+
+    graphql(
+        config,
+        client,
+        MUTATION,
+        serde_json::json!({ "subjectId": node_id }),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Run a GraphQL query against the configured endpoint
+#[cfg(feature = "native")]
+async fn graphql(
+    config: &Config,
+    client: &GitHubClient,
+    query: &str,
+    variables: serde_json::Value,
+) -> Result<serde_json::Value> {
+    client
+        .graphql(&config.github_graphql_url, query, variables)
+        .await
+}
+
 /// Fetch account information to determine type
 #[cfg(feature = "native")]
-pub async fn fetch_account_info(config: &Config, username: &str) -> Result<AccountInfo> {
+pub async fn fetch_account_info(
+    config: &Config,
+    client: &GitHubClient,
+    username: &str,
+) -> Result<AccountInfo> {
     log::debug!("Fetching account info for: {}", username);
 
-    let octocrab = create_client(&config.github_token)?;
-
-    let url = format!("https://api.github.com/users/{}", username);
+    let url = format!("{}/users/{}", config.github_api_url, username);
 
-    let user: serde_json::Value = retry_with_backoff(|| async {
-        octocrab
-            .get::<serde_json::Value, _, ()>(&url, None::<&()>)
-            .await
-            .map_err(|e| GitHubError::RequestFailed {
-                status: 0,
-                message: e.to_string(),
-            })
-    })
-    .await?;
+    let user: serde_json::Value = client.get(&url).await?;
 
     let account_type = match user["type"].as_str() {
         Some("Organization") => AccountType::Organization,
@@ -215,40 +455,355 @@ pub async fn fetch_account_info(config: &Config, username: &str) -> Result<Accou
     })
 }
 
-/// Retry with exponential backoff
+/// Check whether a team exists in an organization
+///
+/// Teams the token cannot see are reported as missing.
 #[cfg(feature = "native")]
-async fn retry_with_backoff<F, Fut, T, E>(mut f: F) -> std::result::Result<T, E>
-where
-    F: FnMut() -> Fut,
-    Fut: std::future::Future<Output = std::result::Result<T, E>>,
-    E: std::fmt::Display,
-{
-    let max_retries = 3;
-    let base_delay = Duration::from_secs(1);
-
-    for attempt in 0..max_retries {
-        match f().await {
-            Ok(value) => return Ok(value),
-            Err(e) => {
-                if attempt < max_retries - 1 {
-                    let delay = base_delay * 2_u32.pow(attempt);
-                    log::warn!(
-                        "Request failed (attempt {}/{}): {}. Retrying in {:?}...",
-                        attempt + 1,
-                        max_retries,
-                        e,
-                        delay
-                    );
-                    tokio::time::sleep(delay).await;
-                } else {
-                    log::error!("Request failed after {} attempts: {}", max_retries, e);
-                    return Err(e);
-                }
-            }
-        }
+pub async fn team_exists(
+    config: &Config,
+    client: &GitHubClient,
+    org: &str,
+    team_slug: &str,
+) -> Result<bool> {
+    log::debug!("Checking team: {}/{}", org, team_slug);
+
+    let url = format!("{}/orgs/{}/teams/{}", config.github_api_url, org, team_slug);
+
+    match client.get(&url).await {
+        Ok(_) => Ok(true),
+        Err(Error::GitHub(GitHubError::NotFound(_))) => Ok(false),
+        Err(e) => Err(e),
     }
+}
+
+/// Fetch a user's permission on a repository
+///
+/// Returns `admin`, `write`, `read` or `none`; users who are not
+/// collaborators have no permission.
+#[cfg(feature = "native")]
+pub async fn fetch_permission(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    username: &str,
+) -> Result<String> {
+    let url = format!(
+        "{}/repos/{}/{}/collaborators/{}/permission",
+        config.github_api_url, repository.owner, repository.name, username
+    );
+
+    match client.get(&url).await {
+        Ok(response) => Ok(response["permission"]
+            .as_str()
+            .unwrap_or("none")
+            .to_string()),
+        Err(Error::GitHub(GitHubError::NotFound(_))) => Ok("none".to_string()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Upload a gzip-compressed, base64-encoded SARIF report to code scanning
+///
+/// Returns the SARIF id used to poll the processing status.
+#[cfg(feature = "native")]
+pub async fn upload_sarif(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    commit_sha: &str,
+    git_ref: &str,
+    encoded_sarif: &str,
+) -> Result<String> {
+    log::info!(
+        "Uploading SARIF to code scanning for {} at {}",
+        git_ref,
+        commit_sha
+    );
+
+    let url = format!(
+        "{}/repos/{}/{}/code-scanning/sarifs",
+        config.github_api_url, repository.owner, repository.name
+    );
 
-    unreachable!()
+    let body = serde_json::json!({
+        "commit_sha": commit_sha,
+        "ref": git_ref,
+        "sarif": encoded_sarif,
+        "tool_name": "gitleaks",
+    });
+
+    let response: serde_json::Value = client.post(&url, &body).await?;
+
+    let sarif_id = response["id"]
+        .as_str()
+        .ok_or_else(|| GitHubError::ParseError("SARIF upload response has no id".to_string()))?
+        .to_string();
+
+    log::debug!("SARIF upload accepted with id {}", sarif_id);
+
+    Ok(sarif_id)
+}
+
+/// Fetch the processing status of a SARIF upload
+#[cfg(feature = "native")]
+pub async fn fetch_sarif_upload_status(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    sarif_id: &str,
+) -> Result<SarifUploadStatus> {
+    let url = format!(
+        "{}/repos/{}/{}/code-scanning/sarifs/{}",
+        config.github_api_url, repository.owner, repository.name, sarif_id
+    );
+
+    let response: serde_json::Value = client.get(&url).await?;
+
+    serde_json::from_value(response).map_err(|e| GitHubError::ParseError(e.to_string()).into())
+}
+
+/// Create an in-progress check run for a commit
+///
+/// Returns the check run id used to add annotations and complete it.
+#[cfg(feature = "native")]
+pub async fn create_check_run(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    name: &str,
+    head_sha: &str,
+) -> Result<u64> {
+    log::info!("Creating check run '{}' for {}", name, head_sha);
+
+    let url = format!(
+        "{}/repos/{}/{}/check-runs",
+        config.github_api_url, repository.owner, repository.name
+    );
+
+    let body = serde_json::json!({
+        "name": name,
+        "head_sha": head_sha,
+        "status": "in_progress",
+    });
+
+    let response: serde_json::Value = client.post(&url, &body).await?;
+
+    response["id"]
+        .as_u64()
+        .ok_or_else(|| GitHubError::ParseError("Check run response has no id".to_string()).into())
+}
+
+/// Update a check run (output, annotations, status or conclusion)
+#[cfg(feature = "native")]
+pub async fn update_check_run(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    check_run_id: u64,
+    body: &serde_json::Value,
+) -> Result<()> {
+    let url = format!(
+        "{}/repos/{}/{}/check-runs/{}",
+        config.github_api_url, repository.owner, repository.name, check_run_id
+    );
+
+    client.patch(&url, body).await?;
+
+    Ok(())
+}
+
+/// Set a commit status on `sha`
+#[cfg(feature = "native")]
+pub async fn create_commit_status(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    sha: &str,
+    status: &CommitStatus,
+) -> Result<()> {
+    log::info!(
+        "Setting commit status '{}' on {}: {:?}",
+        status.context,
+        sha,
+        status.state
+    );
+
+    let url = format!(
+        "{}/repos/{}/{}/statuses/{}",
+        config.github_api_url, repository.owner, repository.name, sha
+    );
+
+    let body = serde_json::to_value(status)?;
+    client.post(&url, &body).await?;
+
+    Ok(())
+}
+
+/// Fetch issues (open and closed) that carry `label`
+///
+/// Pull requests, which the issues endpoint also lists, are left out.
+#[cfg(feature = "native")]
+pub async fn fetch_issues(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    label: &str,
+) -> Result<Vec<serde_json::Value>> {
+    let url = format!(
+        "{}/repos/{}/{}/issues?state=all&labels={}",
+        config.github_api_url, repository.owner, repository.name, label
+    );
+
+    let issues = client.get_all_pages(&url).await?;
+
+    Ok(issues
+        .into_iter()
+        .filter(|issue| issue.get("pull_request").is_none())
+        .collect())
+}
+
+/// Open an issue and return its number
+///
+/// If GitHub rejects the assignees (for example a code owner without access
+/// to the repository), the issue is opened unassigned.
+#[cfg(feature = "native")]
+pub async fn create_issue(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    title: &str,
+    body: &str,
+    labels: &[String],
+    assignees: &[String],
+) -> Result<u64> {
+    log::info!("Opening issue: {}", title);
+
+    let url = format!(
+        "{}/repos/{}/{}/issues",
+        config.github_api_url, repository.owner, repository.name
+    );
+
+    let mut request = serde_json::json!({
+        "title": title,
+        "body": body,
+        "labels": labels,
+        "assignees": assignees,
+    });
+
+    let response = match client.post(&url, &request).await {
+        Err(Error::GitHub(GitHubError::RequestFailed { status: 422, .. }))
+            if !assignees.is_empty() =>
+        {
+            log::warn!(
+                "GitHub rejected issue assignees {:?}, opening unassigned",
+                assignees
+            );
+            request["assignees"] = serde_json::json!([]);
+            client.post(&url, &request).await?
+        }
+        result => result?,
+    };
+
+    response["number"]
+        .as_u64()
+        .ok_or_else(|| GitHubError::ParseError("Issue response has no number".to_string()).into())
+}
+
+/// Update an issue (body, state or state reason)
+#[cfg(feature = "native")]
+pub async fn update_issue(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    issue_number: u64,
+    body: &serde_json::Value,
+) -> Result<()> {
+    let url = format!(
+        "{}/repos/{}/{}/issues/{}",
+        config.github_api_url, repository.owner, repository.name, issue_number
+    );
+
+    client.patch(&url, body).await?;
+
+    Ok(())
+}
+
+/// Comment on an issue
+#[cfg(feature = "native")]
+pub async fn create_issue_comment(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    issue_number: u64,
+    body: &str,
+) -> Result<()> {
+    let url = format!(
+        "{}/repos/{}/{}/issues/{}/comments",
+        config.github_api_url, repository.owner, repository.name, issue_number
+    );
+
+    client
+        .post(&url, &serde_json::json!({ "body": body }))
+        .await?;
+
+    Ok(())
+}
+
+/// Fetch the comments on an issue or pull request
+#[cfg(feature = "native")]
+pub async fn fetch_issue_comments(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    issue_number: u64,
+) -> Result<Vec<serde_json::Value>> {
+    let url = format!(
+        "{}/repos/{}/{}/issues/{}/comments",
+        config.github_api_url, repository.owner, repository.name, issue_number
+    );
+
+    client.get_all_pages(&url).await
+}
+
+/// Replace the body of an issue or pull request comment
+#[cfg(feature = "native")]
+pub async fn update_issue_comment(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    comment_id: u64,
+    body: &str,
+) -> Result<()> {
+    let url = format!(
+        "{}/repos/{}/{}/issues/comments/{}",
+        config.github_api_url, repository.owner, repository.name, comment_id
+    );
+
+    client
+        .patch(&url, &serde_json::json!({ "body": body }))
+        .await?;
+
+    Ok(())
+}
+
+/// Look up the GitHub login of a commit's author
+///
+/// Returns `None` when the author's email is not linked to an account.
+#[cfg(feature = "native")]
+pub async fn fetch_commit_author_login(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    sha: &str,
+) -> Result<Option<String>> {
+    let url = format!(
+        "{}/repos/{}/{}/commits/{}",
+        config.github_api_url, repository.owner, repository.name, sha
+    );
+
+    let commit = client.get(&url).await?;
+
+    Ok(commit["author"]["login"].as_str().map(str::to_string))
 }
 
 /// Check if comment is duplicate
@@ -271,6 +826,19 @@ pub fn is_duplicate_comment(
     false
 }
 
+/// Extract the fingerprint from a comment posted by `build_comment_body`
+///
+/// Returns `None` for comments SecretScout did not post.
+pub fn extract_fingerprint(body: &str) -> Option<&str> {
+    if !body.contains("**Gitleaks Secret Detected**") {
+        return None;
+    }
+
+    let start = body.find("**Fingerprint:** `")? + "**Fingerprint:** `".len();
+    let end = body[start..].find('`')?;
+    Some(&body[start..start + end])
+}
+
 /// Build comment body for a detected secret
 pub fn build_comment_body(
     rule_id: &str,
@@ -327,6 +895,23 @@ mod tests {
         assert!(body.contains("CC:"));
     }
 
+    #[test]
+    fn test_extract_fingerprint() {
+        let body = build_comment_body(
+            "aws-access-token",
+            "abc123",
+            "abc123:src/main.rs:aws-access-token:42",
+            &["@user1".to_string()],
+        );
+
+        assert_eq!(
+            extract_fingerprint(&body),
+            Some("abc123:src/main.rs:aws-access-token:42")
+        );
+        assert_eq!(extract_fingerprint("**Fingerprint:** `abc`"), None);
+        assert_eq!(extract_fingerprint("LGTM"), None);
+    }
+
     #[test]
     fn test_is_duplicate_comment() {
         let existing = vec![serde_json::json!({
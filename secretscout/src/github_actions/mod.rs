@@ -2,7 +2,23 @@
 //!
 //! This module contains the original GitHub Actions logic
 
-use crate::{binary, config::Config, error::Result, events, outputs, sarif};
+use crate::chatops;
+use crate::codeowners::CodeOwners;
+use crate::config::{Config, IssueMode};
+use crate::github::{self, client::GitHubClient};
+use crate::sarif::types::DetectedSecret;
+use crate::{binary, error::Result, events, outputs, report, sarif};
+use std::collections::HashSet;
+use std::io::Write;
+
+/// Findings kept in memory for the check run, PR comments, issues and summaries
+///
+/// Every finding of the report is counted and annotated as it streams from
+/// the SARIF file, but only the first ones are kept, so memory stays bounded
+/// however large the report is. When findings were left out, the outputs that
+/// compare against the complete set (closing issues, resolving PR comments)
+/// are skipped.
+pub const MAX_RETAINED_FINDINGS: usize = 1_000;
 
 /// Run SecretScout in GitHub Actions mode
 pub async fn run(config: &Config) -> Result<i32> {
@@ -11,89 +27,338 @@ pub async fn run(config: &Config) -> Result<i32> {
         env!("CARGO_PKG_VERSION")
     );
 
+    // Comments run commands instead of scanning the event
+    if config.event_name == "issue_comment" {
+        return chatops::run(config).await;
+    }
+
     // Step 1: Parse event context
     log::info!("Parsing event context...");
     let event_context = events::parse_event_context(config).await?;
+
+    Ok(scan(config, &event_context).await?.exit_code)
+}
+
+/// Outcome of a scan
+#[derive(Debug, Clone)]
+pub struct ScanResult {
+    /// Exit code of the action
+    pub exit_code: i32,
+    /// Findings that were reported, at most [`MAX_RETAINED_FINDINGS`]
+    pub findings: Vec<DetectedSecret>,
+    /// Number of findings reported, including those not retained
+    pub findings_count: usize,
+}
+
+/// Findings streamed from the SARIF report
+#[derive(Debug, Default)]
+struct CollectedFindings {
+    /// The first [`MAX_RETAINED_FINDINGS`] findings
+    retained: Vec<DetectedSecret>,
+    /// Findings reported, retained or not
+    total: usize,
+    /// Findings left out because they were acknowledged on the pull request
+    acknowledged: usize,
+    /// Results left out because they lack a location or fingerprints
+    unconvertible: usize,
+}
+
+impl CollectedFindings {
+    /// Whether every reported finding was retained
+    fn is_complete(&self) -> bool {
+        self.retained.len() == self.total && self.unconvertible == 0
+    }
+}
+
+/// Scan the event's range and publish the configured outputs
+pub async fn scan(config: &Config, event_context: &events::EventContext) -> Result<ScanResult> {
     log::info!("Event type: {:?}", event_context.event_type);
     log::info!("Base ref: {}", event_context.base_ref);
     log::info!("Head ref: {}", event_context.head_ref);
 
+    // Fork PRs on `pull_request` get a read-only token
+    let restricted;
+    let config = if event_context.is_read_only() {
+        log::warn!("Pull request from a fork with a read-only token, writing the job summary only");
+        restricted = summary_only(config);
+        &restricted
+    } else {
+        config
+    };
+
+    if config.enable_summary && event_context.has_base_token() {
+        write_summary(
+            config,
+            &outputs::generate_base_token_note(&config.event_name),
+        )?;
+    }
+
     // Step 2: Obtain gitleaks binary
     log::info!("Obtaining gitleaks binary...");
     let binary_path = binary::obtain_binary(config).await?;
     log::info!("Using binary: {}", binary_path.display());
 
-    // Step 3: Build gitleaks arguments
-    let log_opts = events::build_log_opts(&event_context);
-    let args = binary::build_arguments(config, &log_opts);
+    // Step 3: Deepen shallow clones until the scan range is present
+    let history = events::git::History::of(event_context);
+    events::git::ensure_history(&config.workspace_path, &history).await?;
+
+    // Step 4: Build gitleaks arguments, taking the gitleaks config and ignore
+    // file from the base commit when the checkout is untrusted
+    let trusted = if event_context.has_base_token() {
+        let dir = binary::trusted_settings_dir();
+        Some(binary::load_trusted_settings(config, &event_context.base_ref, &dir).await?)
+    } else {
+        None
+    };
+    let log_opts = events::build_log_opts(event_context);
+    let args = binary::build_arguments(config, &log_opts, trusted.as_ref());
     log::debug!("Gitleaks arguments: {:?}", args);
 
-    // Step 4: Execute gitleaks
+    // Step 5: Execute gitleaks
     log::info!("Executing gitleaks scan...");
     let execution_result =
         binary::execute_gitleaks(&binary_path, &args, &config.workspace_path).await?;
 
-    // Step 5: Process results based on exit code
-    match execution_result.exit_code {
+    // Authenticate once for every output that calls the API, after the
+    // scan so a GitHub App token cannot expire while gitleaks runs
+    let unauthenticated;
+    let (config, client) = match github::create_client(config).await {
+        Ok(client) => (config, client),
+        Err(e) if !config.enable_code_scanning_upload => {
+            log::warn!(
+                "Failed to authenticate with GitHub: {}. Writing the job summary only",
+                e
+            );
+            unauthenticated = summary_only(config);
+            (&unauthenticated, GitHubClient::new("")?)
+        }
+        Err(e) => return Err(e),
+    };
+    let client = &client;
+
+    // Step 6: Stream findings from the report, leaving out those acknowledged
+    // on the PR
+    let mut exit_code = execution_result.exit_code;
+    let mut collected = CollectedFindings::default();
+    let mut acknowledged = HashSet::new();
+    let mut code_owners = None;
+    if exit_code == 2 {
+        if event_context.is_pull_request() {
+            acknowledged = chatops::acknowledged_fingerprints(config, client, event_context).await;
+        }
+
+        code_owners = CodeOwners::for_event(config, event_context).await;
+        log::info!("Parsing SARIF report...");
+        collected = collect_findings(config, code_owners.as_ref(), &acknowledged)?;
+        if collected.acknowledged > 0 {
+            log::info!(
+                "{} finding(s) acknowledged on the pull request",
+                collected.acknowledged
+            );
+        }
+        if collected.unconvertible > 0 {
+            log::warn!(
+                "{} result(s) in the SARIF report could not be read as findings",
+                collected.unconvertible
+            );
+        }
+        if collected.retained.len() < collected.total {
+            log::warn!(
+                "Reporting the first {} of {} findings in comments, check runs, issues and summaries",
+                collected.retained.len(),
+                collected.total
+            );
+        }
+        if collected.acknowledged > 0 && collected.total == 0 && collected.unconvertible == 0 {
+            log::info!("Every finding was acknowledged");
+            exit_code = 0;
+        }
+    }
+    let complete = collected.is_complete();
+    let unconvertible = collected.unconvertible;
+    let findings_count = collected.total;
+    let findings = collected.retained;
+
+    // Step 7: Process results based on exit code
+    match exit_code {
         0 => {
             // No secrets found
             log::info!("No secrets detected");
 
+            if config.enable_check_run {
+                publish_check_run(
+                    config,
+                    client,
+                    event_context,
+                    &[],
+                    0,
+                    outputs::CheckConclusion::Success,
+                )
+                .await;
+            }
+
+            if config.enable_commit_status {
+                publish_commit_status(config, client, event_context, outputs::ScanOutcome::Clean)
+                    .await;
+            }
+
+            if config.enable_comments && event_context.is_pull_request() {
+                resolve_stale_comments(config, client, event_context, &[], &acknowledged).await;
+                upsert_summary_comment(config, client, event_context, &[], 0).await;
+            }
+
+            sync_issues(config, client, event_context, &[], true).await;
+
             if config.enable_summary {
                 let summary = outputs::generate_success_summary();
-                outputs::write_summary(&summary)?;
+                write_summary(config, &summary)?;
+            }
+
+            write_requested_report(config, event_context, code_owners.as_ref(), &acknowledged);
+            write_step_outputs(config, &[], 0, 0);
+
+            // Upload the empty report too, so previously open alerts get
+            // closed. Uploads go last: a rejected one fails the run, but
+            // only after every other output was written.
+            if config.enable_code_scanning_upload {
+                upload_code_scanning(config, client, event_context).await?;
             }
 
-            Ok(0)
+            Ok(ScanResult {
+                exit_code: 0,
+                findings: Vec::new(),
+                findings_count: 0,
+            })
         }
         2 => {
             // Secrets detected
             log::warn!("Secrets detected!");
-
-            // Parse SARIF report
-            log::info!("Parsing SARIF report...");
-            let findings = sarif::parse_and_extract(config.sarif_path())?;
-            log::warn!("Found {} secret(s)", findings.len());
+            log::warn!("Found {} secret(s)", findings_count);
 
             // Generate outputs (must complete before exiting)
-            if config.enable_comments
-                && matches!(event_context.event_type, events::EventType::PullRequest)
-            {
+            if config.enable_check_run {
+                publish_check_run(
+                    config,
+                    client,
+                    event_context,
+                    &findings,
+                    findings_count,
+                    outputs::CheckConclusion::Failure,
+                )
+                .await;
+            }
+
+            if config.enable_commit_status {
+                publish_commit_status(
+                    config,
+                    client,
+                    event_context,
+                    outputs::ScanOutcome::Leaks(Some(findings_count)),
+                )
+                .await;
+            }
+
+            if config.enable_comments && event_context.is_pull_request() {
                 log::info!("Posting PR comments...");
-                match outputs::post_pr_comments(config, &event_context, &findings).await {
+                match outputs::post_pr_comments(config, client, event_context, &findings).await {
                     Ok(count) => log::info!("Posted {} comments", count),
                     Err(e) => log::warn!("Failed to post some comments: {}", e),
                 }
+
+                // Comments on findings that were not retained would look stale
+                if complete {
+                    resolve_stale_comments(config, client, event_context, &findings, &acknowledged)
+                        .await;
+                }
+                upsert_summary_comment(config, client, event_context, &findings, findings_count)
+                    .await;
             }
 
+            sync_issues(config, client, event_context, &findings, complete).await;
+
             if config.enable_summary {
                 log::info!("Generating job summary...");
-                let summary =
+                let mut summary =
                     outputs::generate_findings_summary(&event_context.repository, &findings);
-                outputs::write_summary(&summary)?;
+                if findings.len() < findings_count {
+                    summary.push_str(&outputs::generate_truncation_note(
+                        findings.len(),
+                        findings_count,
+                    ));
+                }
+                if unconvertible > 0 {
+                    summary.push_str(&outputs::generate_unconvertible_note(unconvertible));
+                }
+                write_summary(config, &summary)?;
             }
 
             if config.enable_upload_artifact {
-                log::info!(
-                    "SARIF report ready for artifact upload: {}",
-                    config.sarif_path().display()
-                );
+                upload_artifact(config).await;
             }
 
-            // Return 1 to fail the workflow when secrets are found
-            Ok(1)
+            write_requested_report(config, event_context, code_owners.as_ref(), &acknowledged);
+
+            // Return 1 to fail the workflow when secrets are found, unless a
+            // dispatched scan asked to fail only on errors
+            let fail_on = event_context
+                .inputs
+                .as_ref()
+                .map(|inputs| inputs.fail_on)
+                .unwrap_or_default();
+            let exit_code = match fail_on {
+                events::FailOn::Findings => 1,
+                events::FailOn::Error => {
+                    log::info!("fail_on is 'error', not failing the workflow on findings");
+                    0
+                }
+            };
+
+            write_step_outputs(config, &findings, findings_count, exit_code);
+
+            if config.enable_code_scanning_upload {
+                upload_code_scanning(config, client, event_context).await?;
+            }
+
+            Ok(ScanResult {
+                exit_code,
+                findings,
+                findings_count,
+            })
         }
         1 => {
             // Gitleaks error
             log::error!("Gitleaks exited with error code 1");
             log::error!("Stderr: {}", execution_result.stderr);
 
+            if config.enable_check_run {
+                publish_check_run(
+                    config,
+                    client,
+                    event_context,
+                    &[],
+                    0,
+                    outputs::CheckConclusion::Neutral,
+                )
+                .await;
+            }
+
+            if config.enable_commit_status {
+                publish_commit_status(config, client, event_context, outputs::ScanOutcome::Failed)
+                    .await;
+            }
+
             if config.enable_summary {
                 let summary = outputs::generate_error_summary(1);
-                outputs::write_summary(&summary)?;
+                write_summary(config, &summary)?;
             }
 
-            Ok(1)
+            write_step_outputs(config, &[], 0, 1);
+
+            Ok(ScanResult {
+                exit_code: 1,
+                findings: Vec::new(),
+                findings_count: 0,
+            })
         }
         code => {
             // Unexpected exit code
@@ -101,12 +366,445 @@ pub async fn run(config: &Config) -> Result<i32> {
             log::error!("Stdout: {}", execution_result.stdout);
             log::error!("Stderr: {}", execution_result.stderr);
 
+            if config.enable_check_run {
+                publish_check_run(
+                    config,
+                    client,
+                    event_context,
+                    &[],
+                    0,
+                    outputs::CheckConclusion::Neutral,
+                )
+                .await;
+            }
+
+            if config.enable_commit_status {
+                publish_commit_status(config, client, event_context, outputs::ScanOutcome::Failed)
+                    .await;
+            }
+
             if config.enable_summary {
                 let summary = outputs::generate_error_summary(code);
-                outputs::write_summary(&summary)?;
+                write_summary(config, &summary)?;
             }
 
-            Ok(code)
+            write_step_outputs(config, &[], 0, code);
+
+            Ok(ScanResult {
+                exit_code: code,
+                findings: Vec::new(),
+                findings_count: 0,
+            })
         }
     }
 }
+
+/// Stream the findings of the SARIF report
+///
+/// Acknowledged findings are skipped. The rest get their code owners as they
+/// are read, the first [`outputs::MAX_ANNOTATIONS`] are annotated and the
+/// first [`MAX_RETAINED_FINDINGS`] are kept.
+fn collect_findings(
+    config: &Config,
+    code_owners: Option<&CodeOwners>,
+    acknowledged: &HashSet<String>,
+) -> Result<CollectedFindings> {
+    let mut stdout = std::io::stdout().lock();
+    let mut collected = CollectedFindings::default();
+
+    let skipped = for_each_finding(config, code_owners, acknowledged, |finding| {
+        if config.enable_annotations && collected.total < outputs::MAX_ANNOTATIONS {
+            writeln!(stdout, "{}", outputs::format_annotation(&finding))?;
+        }
+
+        collected.total += 1;
+        if collected.retained.len() < MAX_RETAINED_FINDINGS {
+            collected.retained.push(finding);
+        }
+        Ok(())
+    })?;
+    if config.enable_annotations && collected.total > outputs::MAX_ANNOTATIONS {
+        let omitted = collected.total - outputs::MAX_ANNOTATIONS;
+        writeln!(stdout, "{}", outputs::format_omitted_annotations(omitted))?;
+    }
+    collected.acknowledged = skipped.acknowledged;
+    collected.unconvertible = skipped.unconvertible;
+
+    Ok(collected)
+}
+
+/// Results [`for_each_finding`] left out
+#[derive(Debug, Default)]
+struct SkippedResults {
+    /// Findings acknowledged on the pull request
+    acknowledged: usize,
+    /// Results without a location or fingerprints
+    unconvertible: usize,
+}
+
+/// Stream the findings of the SARIF report that were not acknowledged,
+/// with their code owners
+fn for_each_finding(
+    config: &Config,
+    code_owners: Option<&CodeOwners>,
+    acknowledged: &HashSet<String>,
+    mut visit: impl FnMut(DetectedSecret) -> Result<()>,
+) -> Result<SkippedResults> {
+    let mut skipped = SkippedResults::default();
+
+    let mut stream = sarif::stream_findings_from_file(config.sarif_path())?;
+    for finding in stream.by_ref() {
+        let mut finding = finding?;
+        if acknowledged.contains(&finding.fingerprint) {
+            skipped.acknowledged += 1;
+            continue;
+        }
+        if let Some(owners) = code_owners {
+            finding.owners = owners.owners_of(&finding.file_path).to_vec();
+        }
+        visit(finding)?;
+    }
+    skipped.unconvertible = stream.skipped();
+
+    Ok(skipped)
+}
+
+/// Upload the SARIF report as a workflow artifact (non-fatal)
+async fn upload_artifact(config: &Config) {
+    if config.dry_run {
+        println!(
+            "[dry-run] upload artifact {} from {}",
+            config.artifact_name,
+            config.sarif_path().display()
+        );
+        return;
+    }
+    log::info!("Uploading SARIF artifact {}...", config.artifact_name);
+    if let Err(e) = outputs::upload_sarif_artifact(config).await {
+        log::warn!("Failed to upload SARIF artifact: {}", e);
+    }
+}
+
+/// Write the extra report requested by a dispatched scan (non-fatal)
+///
+/// The report lists every finding, so they are streamed from the SARIF
+/// report twice rather than taken from the retained ones: once to count them
+/// for the report's header and once to write them.
+fn write_requested_report(
+    config: &Config,
+    event_context: &events::EventContext,
+    code_owners: Option<&CodeOwners>,
+    acknowledged: &HashSet<String>,
+) {
+    let Some(name) = event_context
+        .inputs
+        .as_ref()
+        .and_then(|inputs| inputs.report_format.as_deref())
+    else {
+        return;
+    };
+    // Validated when the event was parsed
+    let Ok(format) = name.parse::<report::OutputFormat>() else {
+        return;
+    };
+    if format == report::OutputFormat::Sarif {
+        log::info!("SARIF report is at {}", config.sarif_path().display());
+        return;
+    }
+
+    let path = config.report_path(format);
+    let options = report::WriteOptions {
+        repository_url: Some(event_context.repository.html_url.clone()),
+        group_by_owner: false,
+    };
+    let result = stream_report(config, code_owners, acknowledged, format, &options, &path);
+
+    match result {
+        Ok(()) => log::info!("Wrote {} report to {}", name, path.display()),
+        Err(e) => log::warn!("Failed to write {} report: {}", name, e),
+    }
+}
+
+/// Write every finding that was not acknowledged to a report at `path`
+fn stream_report(
+    config: &Config,
+    code_owners: Option<&CodeOwners>,
+    acknowledged: &HashSet<String>,
+    format: report::OutputFormat,
+    options: &report::WriteOptions,
+    path: &std::path::Path,
+) -> Result<()> {
+    let mut totals = report::ReportTotals::default();
+    for_each_finding(config, code_owners, acknowledged, |finding| {
+        totals.add(&finding);
+        Ok(())
+    })?;
+
+    let file = std::fs::File::create(path)
+        .map_err(|e| crate::error::Error::Io(format!("{}: {}", path.display(), e)))?;
+    let mut report =
+        report::ReportWriter::new(format, options, totals, std::io::BufWriter::new(file))?;
+    for_each_finding(config, code_owners, acknowledged, |finding| {
+        report.write(&finding)
+    })?;
+    report.finish()
+}
+
+/// Append to the job summary, or print it in a dry run
+fn write_summary(config: &Config, content: &str) -> Result<()> {
+    if config.dry_run {
+        println!("[dry-run] job summary\n{}", content);
+        return Ok(());
+    }
+    outputs::write_summary(content)?;
+    Ok(())
+}
+
+/// Write step outputs for later workflow steps (non-fatal)
+fn write_step_outputs(
+    config: &Config,
+    findings: &[DetectedSecret],
+    findings_count: usize,
+    exit_code: i32,
+) {
+    let step_outputs =
+        outputs::build_step_outputs(&config.sarif_path(), findings, findings_count, exit_code);
+    if config.dry_run {
+        for (name, value) in &step_outputs {
+            println!("[dry-run] step output {}={}", name, value);
+        }
+        return;
+    }
+    if let Err(e) = outputs::write_step_outputs(&step_outputs) {
+        log::warn!("Failed to write step outputs: {}", e);
+    }
+}
+
+/// Upload the SARIF report to code scanning, failing the run if it is rejected
+async fn upload_code_scanning(
+    config: &Config,
+    client: &GitHubClient,
+    event_context: &events::EventContext,
+) -> Result<()> {
+    if config.dry_run {
+        let (commit_sha, git_ref) = event_context.code_scanning_target(config);
+        println!(
+            "[dry-run] upload {} to code scanning for {} at {}",
+            config.sarif_path().display(),
+            git_ref,
+            commit_sha
+        );
+        return Ok(());
+    }
+    log::info!("Uploading SARIF report to code scanning...");
+    let status = outputs::upload_to_code_scanning(config, client, event_context).await?;
+
+    match status.analyses_url {
+        Some(url) => log::info!("Code scanning analysis available at {}", url),
+        None => log::info!("Code scanning upload processed"),
+    }
+
+    Ok(())
+}
+
+/// Publish the check run (non-fatal, like PR comments)
+async fn publish_check_run(
+    config: &Config,
+    client: &GitHubClient,
+    event_context: &events::EventContext,
+    findings: &[sarif::types::DetectedSecret],
+    findings_count: usize,
+    conclusion: outputs::CheckConclusion,
+) {
+    log::info!("Publishing check run...");
+    match outputs::publish_check_run(
+        config,
+        client,
+        event_context,
+        findings,
+        findings_count,
+        conclusion,
+    )
+    .await
+    {
+        Ok(id) => log::info!("Published check run {}", id),
+        Err(e) => log::warn!("Failed to publish check run: {}", e),
+    }
+}
+
+/// Set the commit status on the scanned head commit (non-fatal)
+async fn publish_commit_status(
+    config: &Config,
+    client: &GitHubClient,
+    event_context: &events::EventContext,
+    outcome: outputs::ScanOutcome,
+) {
+    let head_sha = event_context.head_sha(config);
+
+    log::info!("Setting commit status...");
+    let target_url = config.run_url();
+    if let Err(e) = outputs::publish_commit_status(
+        config,
+        client,
+        &event_context.repository,
+        head_sha,
+        outcome,
+        target_url.as_deref(),
+    )
+    .await
+    {
+        log::warn!("Failed to set commit status: {}", e);
+    }
+}
+
+/// Track findings of default branch scans as issues (non-fatal)
+async fn sync_issues(
+    config: &Config,
+    client: &GitHubClient,
+    event_context: &events::EventContext,
+    findings: &[DetectedSecret],
+    complete: bool,
+) {
+    if config.issue_mode == IssueMode::Off
+        || !matches!(
+            event_context.event_type,
+            events::EventType::Push
+                | events::EventType::Schedule
+                | events::EventType::WorkflowDispatch
+        )
+    {
+        return;
+    }
+
+    log::info!("Syncing tracking issues...");
+    match outputs::sync_issues(config, client, event_context, findings, complete).await {
+        Ok(sync) => log::info!(
+            "Issues: {} opened, {} reopened, {} updated, {} closed",
+            sync.opened,
+            sync.reopened,
+            sync.updated,
+            sync.closed
+        ),
+        Err(e) => log::warn!("Failed to sync tracking issues: {}", e),
+    }
+}
+
+/// Configuration with every output that writes through the API turned off
+fn summary_only(config: &Config) -> Config {
+    Config {
+        enable_comments: false,
+        enable_check_run: false,
+        enable_commit_status: false,
+        enable_code_scanning_upload: false,
+        issue_mode: IssueMode::Off,
+        ..config.clone()
+    }
+}
+
+/// Create or update the sticky PR summary comment (non-fatal)
+async fn upsert_summary_comment(
+    config: &Config,
+    client: &GitHubClient,
+    event_context: &events::EventContext,
+    findings: &[sarif::types::DetectedSecret],
+    findings_count: usize,
+) {
+    if let Err(e) =
+        outputs::upsert_summary_comment(config, client, event_context, findings, findings_count)
+            .await
+    {
+        log::warn!("Failed to update summary comment: {}", e);
+    }
+}
+
+/// Mark PR comments for findings that are gone as outdated (non-fatal)
+async fn resolve_stale_comments(
+    config: &Config,
+    client: &GitHubClient,
+    event_context: &events::EventContext,
+    findings: &[sarif::types::DetectedSecret],
+    acknowledged: &HashSet<String>,
+) {
+    match outputs::resolve_stale_comments(config, client, event_context, findings, acknowledged)
+        .await
+    {
+        Ok(0) => {}
+        Ok(count) => log::info!("Resolved {} stale comments", count),
+        Err(e) => log::warn!("Failed to resolve stale comments: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn result(line: usize) -> serde_json::Value {
+        json!({
+            "ruleId": "generic-api-key",
+            "message": { "text": "generic-api-key has detected secret" },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": "src/keys.rs" },
+                    "region": { "startLine": line }
+                }
+            }],
+            "partialFingerprints": { "commitSha": "abc123", "author": "Jane", "email": "j@example.com", "date": "2025-10-16" }
+        })
+    }
+
+    #[test]
+    fn test_collect_findings_retains_a_bounded_number() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        let mut config =
+            Config::for_repository("owner/repo", workspace.path().to_path_buf()).unwrap();
+        config.enable_annotations = false;
+
+        let total = MAX_RETAINED_FINDINGS + 5;
+        let report = json!({
+            "version": "2.1.0",
+            "runs": [{
+                "tool": { "driver": { "name": "gitleaks" } },
+                "results": (1..=total).map(result).collect::<Vec<_>>()
+            }]
+        });
+        std::fs::write(config.sarif_path(), report.to_string()).unwrap();
+
+        let acknowledged = HashSet::from(["abc123:src/keys.rs:generic-api-key:1".to_string()]);
+        let collected = collect_findings(&config, None, &acknowledged).unwrap();
+
+        assert_eq!(collected.acknowledged, 1);
+        assert_eq!(collected.total, total - 1);
+        assert_eq!(collected.retained.len(), MAX_RETAINED_FINDINGS);
+        assert_eq!(collected.retained[0].line_number, 2);
+        assert!(!collected.is_complete());
+    }
+
+    #[test]
+    fn test_collect_findings_counts_unconvertible_results() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        let mut config =
+            Config::for_repository("owner/repo", workspace.path().to_path_buf()).unwrap();
+        config.enable_annotations = false;
+
+        let mut unconvertible = result(1);
+        unconvertible
+            .as_object_mut()
+            .unwrap()
+            .remove("partialFingerprints");
+        let report = json!({
+            "version": "2.1.0",
+            "runs": [{
+                "tool": { "driver": { "name": "gitleaks" } },
+                "results": [unconvertible]
+            }]
+        });
+        std::fs::write(config.sarif_path(), report.to_string()).unwrap();
+
+        let collected = collect_findings(&config, None, &HashSet::new()).unwrap();
+
+        assert_eq!(collected.total, 0);
+        assert_eq!(collected.unconvertible, 1);
+        assert!(!collected.is_complete());
+    }
+}
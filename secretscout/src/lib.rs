@@ -45,20 +45,22 @@
 //! into your own Rust applications:
 //!
 //! ```no_run
-//! use secretscout::{Config, commands};
+//! use secretscout::commands;
+//! use std::path::Path;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     // Scan a repository for secrets
 //!     commands::detect(
-//!         ".",                    // source path
-//!         "results.sarif",        // report path
-//!         "sarif",                // format
-//!         false,                  // redact
-//!         2,                      // exit code on findings
-//!         None,                   // log options
-//!         None,                   // config path
-//!         false,                  // verbose
+//!         Path::new("."),             // source path
+//!         Path::new("results.sarif"), // report path
+//!         "sarif",                    // format
+//!         false,                      // redact
+//!         2,                          // exit code on findings
+//!         None,                       // log options
+//!         None,                       // config path
+//!         false,                      // verbose
+//!         None,                       // GitHub commit status
 //!     ).await?;
 //!     Ok(())
 //! }
@@ -86,6 +88,12 @@ pub mod error;
 #[cfg(feature = "native")]
 pub mod binary;
 
+#[cfg(feature = "native")]
+pub mod chatops;
+
+#[cfg(feature = "native")]
+pub mod codeowners;
+
 #[cfg(feature = "native")]
 pub mod config;
 
@@ -101,6 +109,9 @@ pub mod outputs;
 #[cfg(feature = "native")]
 pub mod github;
 
+#[cfg(feature = "native")]
+pub mod report;
+
 // CLI-specific modules
 #[cfg(feature = "native")]
 pub mod cli;
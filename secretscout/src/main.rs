@@ -54,7 +54,8 @@ fn detect_mode() -> Mode {
 }
 
 async fn run_cli_mode() -> error::Result<i32> {
-    use secretscout::cli::{Cli, Commands};
+    use secretscout::cli::{Cli, Commands, SarifCommands};
+    use secretscout::commands::detect::GitHubStatus;
 
     let cli = Cli::parse_args();
 
@@ -66,24 +67,38 @@ async fn run_cli_mode() -> error::Result<i32> {
 
     match cli.command {
         Commands::Detect {
-            source,
-            report_path,
-            report_format,
+            ref source,
+            ref report_path,
+            ref report_format,
             redact,
             exit_code,
-            log_opts,
+            ref log_opts,
             verbose,
+            github_status,
+            ref repository,
+            ref sha,
+            ref target_url,
         } => {
+            let github_status = match repository {
+                Some(repository) if github_status => Some(GitHubStatus {
+                    config: cli.github_config(repository, source.clone())?,
+                    sha: sha.clone(),
+                    target_url: target_url.clone(),
+                }),
+                _ => None,
+            };
+
             // Run gitleaks detect
             secretscout::commands::detect(
-                &source,
-                &report_path,
-                &report_format,
+                source,
+                report_path,
+                report_format,
                 redact,
                 exit_code,
                 log_opts.as_deref(),
                 cli.config.as_deref(),
                 verbose,
+                github_status.as_ref(),
             )
             .await?;
             Ok(0)
@@ -99,6 +114,56 @@ async fn run_cli_mode() -> error::Result<i32> {
             Ok(0)
         }
 
+        Commands::Convert {
+            from,
+            to,
+            input,
+            output,
+            repository_url,
+            repository,
+            source,
+            group_by_owner,
+        } => {
+            let repository_url = repository_url.or_else(|| {
+                repository
+                    .map(|repo| format!("{}/{}", cli.github_server_url.trim_end_matches('/'), repo))
+            });
+            secretscout::commands::convert(
+                &from,
+                &to,
+                &input,
+                &output,
+                repository_url,
+                source.as_deref(),
+                group_by_owner,
+            )?;
+            Ok(0)
+        }
+
+        Commands::Action {
+            ref event_file,
+            ref event_name,
+            ref repository,
+            ref source,
+            ref sha,
+            dry_run,
+            check_run,
+            commit_status,
+            code_scanning_upload,
+        } => {
+            let mut config = cli.action_config(repository, source.clone(), dry_run)?;
+            config.enable_check_run = check_run;
+            config.enable_commit_status = commit_status;
+            config.enable_code_scanning_upload = code_scanning_upload;
+
+            secretscout::commands::action(config, event_file, event_name, sha.as_deref(), dry_run)
+                .await
+        }
+
+        Commands::Sarif { command } => match command {
+            SarifCommands::Validate { path } => secretscout::commands::sarif::validate(&path),
+        },
+
         Commands::Version => {
             println!("secretscout {}", env!("CARGO_PKG_VERSION"));
             Ok(0)
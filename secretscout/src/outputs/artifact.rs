@@ -0,0 +1,310 @@
+//! Artifact upload module
+//!
+//! Uploads the SARIF report as a workflow artifact through the Actions
+//! results service, as `actions/upload-artifact@v4` does: the artifact is
+//! created, the zipped report is uploaded to the returned signed URL, and the
+//! artifact is finalized with its size and SHA-256 hash. The report is
+//! zipped into a temporary file and uploaded from there, so it is never held
+//! in memory.
+
+use crate::config::Config;
+use crate::error::{ConfigError, GitHubError, Result, SarifError};
+use crate::github::client::GitHubClient;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+
+/// Twirp service of the artifact API
+const ARTIFACT_SERVICE: &str = "twirp/github.actions.results.api.v1.ArtifactService";
+
+/// Version of the artifact backend
+const ARTIFACT_VERSION: u32 = 4;
+
+/// Client for the Actions artifact API of the current job
+#[derive(Debug, Clone)]
+pub struct ArtifactClient {
+    results_url: String,
+    runtime_token: String,
+    run_backend_id: String,
+    job_backend_id: String,
+}
+
+impl ArtifactClient {
+    /// Create a client from the results service URL and the job's runtime token
+    pub fn new(results_url: &str, runtime_token: &str) -> Result<Self> {
+        let (run_backend_id, job_backend_id) = backend_ids(runtime_token)?;
+
+        Ok(Self {
+            results_url: results_url.trim_end_matches('/').to_string(),
+            runtime_token: runtime_token.to_string(),
+            run_backend_id,
+            job_backend_id,
+        })
+    }
+
+    /// Create a client from `ACTIONS_RESULTS_URL` and `ACTIONS_RUNTIME_TOKEN`
+    pub fn from_env() -> Result<Self> {
+        let results_url = std::env::var("ACTIONS_RESULTS_URL")
+            .map_err(|_| ConfigError::missing_env("ACTIONS_RESULTS_URL"))?;
+        let runtime_token = std::env::var("ACTIONS_RUNTIME_TOKEN")
+            .map_err(|_| ConfigError::missing_env("ACTIONS_RUNTIME_TOKEN"))?;
+        Self::new(&results_url, &runtime_token)
+    }
+
+    /// Upload a file as a zipped artifact and return the artifact id
+    pub async fn upload_file(
+        &self,
+        name: &str,
+        path: &Path,
+        retention_days: Option<u32>,
+    ) -> Result<String> {
+        let source =
+            File::open(path).map_err(|_| SarifError::FileNotFound(path.display().to_string()))?;
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| name.to_string());
+        let archive = zip_file(&file_name, source)?;
+
+        let client = GitHubClient::new(&self.runtime_token)?;
+
+        let mut create = serde_json::json!({
+            "workflow_run_backend_id": self.run_backend_id,
+            "workflow_job_run_backend_id": self.job_backend_id,
+            "name": name,
+            "version": ARTIFACT_VERSION,
+        });
+        if let Some(days) = retention_days {
+            create["expires_at"] = serde_json::json!(expires_at(days));
+        }
+
+        let response = client
+            .post(&self.method_url("CreateArtifact"), &create)
+            .await?;
+        check_ok(&response, "CreateArtifact")?;
+        let upload_url = response_field(&response, "signed_upload_url", "signedUploadUrl")
+            .ok_or_else(|| GitHubError::ParseError("missing signed_upload_url".to_string()))?;
+
+        log::debug!("Uploading {} bytes for artifact {}", archive.size, name);
+        let finalize = serde_json::json!({
+            "workflow_run_backend_id": self.run_backend_id,
+            "workflow_job_run_backend_id": self.job_backend_id,
+            "name": name,
+            "size": archive.size.to_string(),
+            "hash": archive.hash,
+        });
+        upload_blob(&upload_url, archive).await?;
+
+        let response = client
+            .post(&self.method_url("FinalizeArtifact"), &finalize)
+            .await?;
+        check_ok(&response, "FinalizeArtifact")?;
+        let artifact_id = response_field(&response, "artifact_id", "artifactId")
+            .ok_or_else(|| GitHubError::ParseError("missing artifact_id".to_string()))?;
+
+        log::info!("Uploaded artifact {} (id {})", name, artifact_id);
+        Ok(artifact_id)
+    }
+
+    fn method_url(&self, method: &str) -> String {
+        format!("{}/{}/{}", self.results_url, ARTIFACT_SERVICE, method)
+    }
+}
+
+/// Upload the workspace SARIF report as the configured artifact
+pub async fn upload_sarif_artifact(config: &Config) -> Result<String> {
+    ArtifactClient::from_env()?
+        .upload_file(
+            &config.artifact_name,
+            &config.sarif_path(),
+            config.artifact_retention_days,
+        )
+        .await
+}
+
+/// Read the run and job backend ids from the runtime token's scopes
+///
+/// The token is a JWT whose `scp` claim contains
+/// `Actions.Results:<run backend id>:<job backend id>`.
+fn backend_ids(runtime_token: &str) -> Result<(String, String)> {
+    let invalid = || ConfigError::invalid_env("ACTIONS_RUNTIME_TOKEN", "<redacted>");
+
+    let payload = runtime_token.split('.').nth(1).ok_or_else(invalid)?;
+    let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|_| invalid())?;
+    let claims: serde_json::Value = serde_json::from_slice(&payload).map_err(|_| invalid())?;
+
+    let scopes = claims["scp"].as_str().ok_or_else(invalid)?;
+    scopes
+        .split(' ')
+        .find_map(|scope| {
+            let mut parts = scope.split(':');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("Actions.Results"), Some(run), Some(job)) => {
+                    Some((run.to_string(), job.to_string()))
+                }
+                _ => None,
+            }
+        })
+        .ok_or_else(|| invalid().into())
+}
+
+/// Zipped artifact in a temporary file, rewound for reading
+struct Archive {
+    file: File,
+    size: u64,
+    /// Hash in the `sha256:<hex>` form the artifact service expects
+    hash: String,
+}
+
+/// Zip a single file into a temporary file
+///
+/// The zip writer seeks back to fill in each entry's header, so the archive
+/// is hashed in one pass once it is complete rather than as it is written.
+fn zip_file(file_name: &str, mut contents: impl Read) -> Result<Archive> {
+    let failed = |e: &dyn std::fmt::Display| {
+        crate::error::Error::Io(format!("failed to zip artifact: {}", e))
+    };
+
+    let file = tempfile::tempfile_in(archive_dir()).map_err(|e| failed(&e))?;
+    let mut writer = zip::ZipWriter::new(file);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    writer
+        .start_file(file_name, options)
+        .map_err(|e| failed(&e))?;
+    std::io::copy(&mut contents, &mut writer).map_err(|e| failed(&e))?;
+    let mut file = writer.finish().map_err(|e| failed(&e))?;
+
+    file.rewind()?;
+    let mut hasher = Sha256::new();
+    let size = std::io::copy(&mut file, &mut hasher)?;
+    file.rewind()?;
+
+    Ok(Archive {
+        file,
+        size,
+        hash: format!("sha256:{:x}", hasher.finalize()),
+    })
+}
+
+/// Directory for temporary archives, the runner's temp directory in Actions
+fn archive_dir() -> PathBuf {
+    std::env::var_os("RUNNER_TEMP")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// RFC 3339 expiry time `days` from now
+fn expires_at(days: u32) -> String {
+    (chrono::Utc::now() + chrono::Duration::days(i64::from(days)))
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Fail unless the response reports `ok: true`
+fn check_ok(response: &serde_json::Value, method: &str) -> Result<()> {
+    if response["ok"].as_bool() == Some(true) {
+        Ok(())
+    } else {
+        Err(GitHubError::ArtifactRejected(format!("{} returned ok=false", method)).into())
+    }
+}
+
+/// Read a response field by its proto or JSON name, as a string
+fn response_field(
+    response: &serde_json::Value,
+    proto_name: &str,
+    json_name: &str,
+) -> Option<String> {
+    let value = response
+        .get(proto_name)
+        .or_else(|| response.get(json_name))?;
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// PUT the archive to the signed blob storage URL, streaming it from its file
+async fn upload_blob(url: &str, archive: Archive) -> Result<()> {
+    let response = reqwest::Client::new()
+        .put(url)
+        .header("x-ms-blob-type", "BlockBlob")
+        .header(reqwest::header::CONTENT_TYPE, "application/zip")
+        .header(reqwest::header::CONTENT_LENGTH, archive.size)
+        .body(tokio::fs::File::from_std(archive.file))
+        .send()
+        .await
+        .map_err(|e| GitHubError::NetworkError(e.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let message = response.text().await.unwrap_or_default();
+        return Err(GitHubError::RequestFailed {
+            status: status.as_u16(),
+            message,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runtime_token(scopes: &str) -> String {
+        let encode = |value: serde_json::Value| {
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(value.to_string())
+        };
+        format!(
+            "{}.{}.signature",
+            encode(serde_json::json!({ "alg": "RS256" })),
+            encode(serde_json::json!({ "scp": scopes }))
+        )
+    }
+
+    #[test]
+    fn test_backend_ids() {
+        let token = runtime_token("Actions.ExampleScope Actions.Results:run-id:job-id");
+        assert_eq!(
+            backend_ids(&token).unwrap(),
+            ("run-id".to_string(), "job-id".to_string())
+        );
+
+        assert!(backend_ids(&runtime_token("Actions.ExampleScope")).is_err());
+        assert!(backend_ids("not-a-jwt").is_err());
+    }
+
+    #[test]
+    fn test_zip_file() {
+        let mut archive = zip_file("results.sarif", &b"{}"[..]).unwrap();
+        let mut bytes = Vec::new();
+        archive.file.read_to_end(&mut bytes).unwrap();
+        assert_eq!(archive.size, bytes.len() as u64);
+        assert_eq!(archive.hash, format!("sha256:{:x}", Sha256::digest(&bytes)));
+
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        let mut file = zip.by_name("results.sarif").unwrap();
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "{}");
+    }
+
+    #[test]
+    fn test_response_field() {
+        let response = serde_json::json!({ "ok": true, "artifactId": "42" });
+        assert_eq!(
+            response_field(&response, "artifact_id", "artifactId").as_deref(),
+            Some("42")
+        );
+        assert!(check_ok(&response, "FinalizeArtifact").is_ok());
+        assert!(check_ok(&serde_json::json!({ "ok": false }), "FinalizeArtifact").is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! Check run module
+//!
+//! Publishes scan results as a `SecretScout` check run with a line
+//! annotation per finding. Unlike PR comments, check runs work for every
+//! event type and can be marked as required in branch protection.
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::events::EventContext;
+use crate::github::client::GitHubClient;
+use crate::github::{self, CheckAnnotation};
+use crate::sarif::types::DetectedSecret;
+
+/// Name of the check run shown on commits and pull requests
+pub const CHECK_RUN_NAME: &str = "SecretScout";
+
+/// Maximum number of annotations the API accepts per request
+pub const ANNOTATIONS_PER_REQUEST: usize = 50;
+
+/// Final conclusion of the check run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckConclusion {
+    /// Scan completed without findings
+    Success,
+    /// Scan detected secrets
+    Failure,
+    /// Scan could not complete (gitleaks error)
+    Neutral,
+}
+
+impl CheckConclusion {
+    /// API value of the conclusion
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckConclusion::Success => "success",
+            CheckConclusion::Failure => "failure",
+            CheckConclusion::Neutral => "neutral",
+        }
+    }
+}
+
+/// Publish a check run for the scanned commit
+///
+/// Annotations are sent in batches of [`ANNOTATIONS_PER_REQUEST`]; the last
+/// batch is sent together with the conclusion. `findings_count` also counts
+/// findings left out of `findings`, which get no annotation. Returns the
+/// check run id.
+#[cfg(feature = "native")]
+pub async fn publish_check_run(
+    config: &Config,
+    client: &GitHubClient,
+    context: &EventContext,
+    findings: &[DetectedSecret],
+    findings_count: usize,
+    conclusion: CheckConclusion,
+) -> Result<u64> {
+    let head_sha = context.head_sha(config);
+
+    let check_run_id = github::create_check_run(
+        config,
+        client,
+        &context.repository,
+        CHECK_RUN_NAME,
+        head_sha,
+    )
+    .await?;
+
+    let title = build_title(findings_count, conclusion);
+    let summary = build_summary(findings, findings_count, conclusion);
+    let annotations: Vec<CheckAnnotation> = findings.iter().map(build_annotation).collect();
+    let mut batches: Vec<&[CheckAnnotation]> =
+        annotations.chunks(ANNOTATIONS_PER_REQUEST).collect();
+    let last_batch = batches.pop().unwrap_or(&[]);
+
+    for batch in batches {
+        let body = serde_json::json!({
+            "output": {
+                "title": title,
+                "summary": summary,
+                "annotations": batch,
+            }
+        });
+        github::update_check_run(config, client, &context.repository, check_run_id, &body).await?;
+    }
+
+    let body = serde_json::json!({
+        "status": "completed",
+        "conclusion": conclusion.as_str(),
+        "output": {
+            "title": title,
+            "summary": summary,
+            "annotations": last_batch,
+        }
+    });
+    github::update_check_run(config, client, &context.repository, check_run_id, &body).await?;
+
+    log::info!(
+        "Completed check run {} ({}) with {} annotations",
+        check_run_id,
+        conclusion.as_str(),
+        annotations.len()
+    );
+
+    Ok(check_run_id)
+}
+
+/// Build the annotation for a finding
+pub fn build_annotation(finding: &DetectedSecret) -> CheckAnnotation {
+    CheckAnnotation {
+        path: finding.file_path.clone(),
+        start_line: finding.line_number,
+        end_line: finding.line_number,
+        annotation_level: "failure".to_string(),
+        title: format!("Secret detected: {}", finding.rule_id),
+        message: format!(
+            "Commit: {}\nFingerprint: {}\n\nTo ignore this finding, add the fingerprint to .gitleaksignore.",
+            finding.commit_sha, finding.fingerprint
+        ),
+    }
+}
+
+fn build_title(count: usize, conclusion: CheckConclusion) -> String {
+    match conclusion {
+        CheckConclusion::Success => "No secrets detected".to_string(),
+        CheckConclusion::Failure => format!("{} secret(s) detected", count),
+        CheckConclusion::Neutral => "Scan did not complete".to_string(),
+    }
+}
+
+fn build_summary(
+    findings: &[DetectedSecret],
+    findings_count: usize,
+    conclusion: CheckConclusion,
+) -> String {
+    match conclusion {
+        CheckConclusion::Success => "✅ No leaks detected.".to_string(),
+        CheckConclusion::Neutral => {
+            "⚠️ Gitleaks exited with an error. See the workflow logs for details.".to_string()
+        }
+        CheckConclusion::Failure => {
+            let mut files: Vec<&str> = findings.iter().map(|f| f.file_path.as_str()).collect();
+            files.sort_unstable();
+            files.dedup();
+
+            if findings.len() < findings_count {
+                format!(
+                    "🛑 Gitleaks detected {} secret(s). The first {}, in {} file(s), \
+                     are annotated on the affected lines. The SARIF report lists them all.",
+                    findings_count,
+                    findings.len(),
+                    files.len()
+                )
+            } else {
+                format!(
+                    "🛑 Gitleaks detected {} secret(s) in {} file(s). \
+                     Each finding is annotated on the affected line.",
+                    findings.len(),
+                    files.len()
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(file: &str, line: u32) -> DetectedSecret {
+        DetectedSecret {
+            rule_id: "aws-access-token".to_string(),
+            file_path: file.to_string(),
+            line_number: line,
+            commit_sha: "abc123".to_string(),
+            author: "Jane".to_string(),
+            email: "jane@example.com".to_string(),
+            date: "2025-10-16".to_string(),
+            fingerprint: format!("abc123:{}:aws-access-token:{}", file, line),
+            owners: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_build_annotation() {
+        let annotation = build_annotation(&finding("src/config.rs", 42));
+
+        assert_eq!(annotation.path, "src/config.rs");
+        assert_eq!(annotation.start_line, 42);
+        assert_eq!(annotation.end_line, 42);
+        assert_eq!(annotation.annotation_level, "failure");
+        assert!(annotation.title.contains("aws-access-token"));
+        assert!(annotation
+            .message
+            .contains("abc123:src/config.rs:aws-access-token:42"));
+    }
+
+    #[test]
+    fn test_build_summary() {
+        let findings = vec![finding("a.rs", 1), finding("a.rs", 2), finding("b.rs", 3)];
+
+        assert!(build_summary(&findings, 3, CheckConclusion::Failure)
+            .contains("3 secret(s) in 2 file(s)"));
+        assert!(build_summary(&findings, 1500, CheckConclusion::Failure)
+            .contains("1500 secret(s). The first 3, in 2 file(s)"));
+        assert_eq!(
+            build_title(3, CheckConclusion::Failure),
+            "3 secret(s) detected"
+        );
+        assert_eq!(CheckConclusion::Neutral.as_str(), "neutral");
+    }
+}
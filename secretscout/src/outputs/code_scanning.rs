@@ -0,0 +1,285 @@
+//! Code scanning upload module
+//!
+//! Uploads the SARIF report to GitHub code scanning so findings show up in
+//! the repository's Security tab, then waits for GitHub to finish processing
+//! it so that rejected uploads fail the run instead of disappearing silently.
+
+use crate::config::Config;
+use crate::error::{ConfigError, GitHubError, Result, SarifError};
+use crate::events::EventContext;
+use crate::github::client::GitHubClient;
+use crate::github::{self, SarifProcessingStatus, SarifUploadStatus};
+use crate::sarif::stream::{JsonWalker, Token};
+use base64::Engine;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Interval between processing status checks
+pub const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Maximum number of processing status checks before giving up
+pub const MAX_POLL_ATTEMPTS: u32 = 60;
+
+/// Largest gzip-compressed SARIF payload code scanning accepts
+pub const MAX_COMPRESSED_SIZE: usize = 10 * 1024 * 1024;
+
+/// Largest SARIF report that is compressed for upload
+///
+/// Gitleaks reports compress by well under this factor, so a larger report
+/// cannot fit in [`MAX_COMPRESSED_SIZE`] and is rejected before it is read.
+pub const MAX_REPORT_SIZE: u64 = 20 * MAX_COMPRESSED_SIZE as u64;
+
+/// Upload the workspace SARIF report and wait for it to be processed
+pub async fn upload_to_code_scanning(
+    config: &Config,
+    client: &GitHubClient,
+    context: &EventContext,
+) -> Result<SarifUploadStatus> {
+    upload_sarif_report(config, client, context, &config.sarif_path(), POLL_INTERVAL).await
+}
+
+/// Upload a SARIF report and poll its processing status every `poll_interval`
+///
+/// The report is uploaded under the commit and ref of
+/// [`EventContext::code_scanning_target`].
+pub async fn upload_sarif_report(
+    config: &Config,
+    client: &GitHubClient,
+    context: &EventContext,
+    sarif_path: &Path,
+    poll_interval: Duration,
+) -> Result<SarifUploadStatus> {
+    let (commit_sha, git_ref) = context.code_scanning_target(config);
+    if commit_sha.is_empty() {
+        return Err(ConfigError::MissingEnvVar("GITHUB_SHA".to_string()).into());
+    }
+    if git_ref.is_empty() {
+        return Err(ConfigError::MissingEnvVar("GITHUB_REF".to_string()).into());
+    }
+    let repository = &context.repository;
+
+    let not_found = |_| SarifError::FileNotFound(sarif_path.display().to_string());
+    let size = std::fs::metadata(sarif_path).map_err(not_found)?.len();
+    if size > MAX_REPORT_SIZE {
+        return Err(GitHubError::SarifRejected(format!(
+            "report is {} bytes, too large to fit the {} bytes code scanning accepts after compression",
+            size, MAX_COMPRESSED_SIZE
+        ))
+        .into());
+    }
+
+    let file = File::open(sarif_path).map_err(not_found)?;
+    let encoded = encode_sarif(file, config.code_scanning_category.as_deref())?;
+
+    let sarif_id =
+        github::upload_sarif(config, client, repository, &commit_sha, &git_ref, &encoded).await?;
+
+    for attempt in 1..=MAX_POLL_ATTEMPTS {
+        let status =
+            github::fetch_sarif_upload_status(config, client, repository, &sarif_id).await?;
+
+        match status.processing_status {
+            SarifProcessingStatus::Complete => {
+                log::info!("SARIF upload {} processed by code scanning", sarif_id);
+                return Ok(status);
+            }
+            SarifProcessingStatus::Failed => {
+                let errors = status.errors.unwrap_or_default();
+                let message = if errors.is_empty() {
+                    format!("upload {} failed without details", sarif_id)
+                } else {
+                    errors.join("; ")
+                };
+                return Err(GitHubError::SarifRejected(message).into());
+            }
+            SarifProcessingStatus::Pending => {
+                log::debug!(
+                    "SARIF upload {} still processing (check {}/{})",
+                    sarif_id,
+                    attempt,
+                    MAX_POLL_ATTEMPTS
+                );
+                tokio::time::sleep(poll_interval).await;
+            }
+        }
+    }
+
+    Err(GitHubError::Timeout(format!(
+        "SARIF upload {} was still processing after {} checks",
+        sarif_id, MAX_POLL_ATTEMPTS
+    ))
+    .into())
+}
+
+/// Gzip and base64-encode a SARIF report for the code scanning API
+///
+/// The report is compressed as it is read. When a category is given it is
+/// recorded as `runs[].automationDetails.id` (with the trailing slash code
+/// scanning expects), unless a run already carries its own automation
+/// details.
+pub fn encode_sarif(reader: impl Read, category: Option<&str>) -> Result<String> {
+    let mut encoder = GzEncoder::new(LimitedBuffer::default(), Compression::default());
+
+    match category {
+        Some(category) => copy_with_category(reader, &mut encoder, category)?,
+        None => {
+            std::io::copy(&mut BufReader::new(reader), &mut encoder).map_err(write_error)?;
+        }
+    }
+    let compressed = encoder.finish().map_err(write_error)?;
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(compressed.0))
+}
+
+/// In-memory buffer that refuses to grow past [`MAX_COMPRESSED_SIZE`]
+#[derive(Default)]
+struct LimitedBuffer(Vec<u8>);
+
+impl Write for LimitedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.0.len() + buf.len() > MAX_COMPRESSED_SIZE {
+            return Err(std::io::Error::from(ErrorKind::FileTooLarge));
+        }
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Error for a failure to compress the report
+fn write_error(e: std::io::Error) -> crate::error::Error {
+    if e.kind() == ErrorKind::FileTooLarge {
+        GitHubError::SarifRejected(format!(
+            "report is over {} bytes after compression, which code scanning does not accept",
+            MAX_COMPRESSED_SIZE
+        ))
+        .into()
+    } else {
+        e.into()
+    }
+}
+
+/// Copy a report, recording the analysis category on every run
+///
+/// The report is copied token by token as the SARIF stream reads it, and
+/// `automationDetails` is appended to each `runs[]` object that lacks it, so
+/// the report is never held in memory.
+fn copy_with_category(reader: impl Read, writer: &mut impl Write, category: &str) -> Result<()> {
+    let id = if category.ends_with('/') {
+        category.to_string()
+    } else {
+        format!("{}/", category)
+    };
+    let details = format!("\"automationDetails\":{}", serde_json::json!({ "id": id }));
+
+    let mut walker = JsonWalker::new(reader);
+    walker.record();
+    let mut has_members = false;
+    let mut has_automation = false;
+
+    while let Some(token) = walker.next_token()? {
+        match token {
+            Token::Start(open) => {
+                if open == b'{' && walker.at_run_position() {
+                    has_members = false;
+                    has_automation = false;
+                }
+                walker.enter(open)?;
+            }
+            Token::Key(key) if walker.depth() == 3 && walker.in_runs() => {
+                has_members = true;
+                has_automation |= key == b"automationDetails";
+            }
+            Token::End(frame) if frame.is_object && walker.at_run_position() && !has_automation => {
+                // Insert before the closing brace of the run
+                let recorded = walker.take_recorded();
+                let (before, brace) = recorded.split_at(recorded.len() - 1);
+                let separator = if has_members { "," } else { "" };
+                writer.write_all(before).map_err(write_error)?;
+                writer
+                    .write_all(format!("{}{}", separator, details).as_bytes())
+                    .map_err(write_error)?;
+                writer.write_all(brace).map_err(write_error)?;
+                continue;
+            }
+            _ => {}
+        }
+        writer
+            .write_all(&walker.take_recorded())
+            .map_err(write_error)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    fn decode(encoded: &str) -> serde_json::Value {
+        let compressed = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .unwrap();
+        let mut json = String::new();
+        GzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut json)
+            .unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_encode_sarif_round_trip() {
+        let sarif = br#"{"version":"2.1.0","runs":[{"tool":{"driver":{"name":"gitleaks"}}}]}"#;
+        let decoded = decode(&encode_sarif(&sarif[..], None).unwrap());
+
+        assert_eq!(decoded["version"], "2.1.0");
+        assert!(decoded["runs"][0].get("automationDetails").is_none());
+    }
+
+    #[test]
+    fn test_encode_sarif_with_category() {
+        let sarif = br#"{"version":"2.1.0","runs":[
+            {"tool":{"driver":{"name":"gitleaks"}},"results":[{"ruleId":"a\"}{"}]},
+            {"tool":{"driver":{"name":"gitleaks"}},"automationDetails":{"id":"custom/"}},
+            {}
+        ],"properties":{"runs":[{}]}}"#;
+        // A tiny buffer makes runs straddle reads
+        let reader = BufReader::with_capacity(7, &sarif[..]);
+        let decoded = decode(&encode_sarif(reader, Some("secrets")).unwrap());
+
+        assert_eq!(decoded["runs"][0]["automationDetails"]["id"], "secrets/");
+        assert_eq!(decoded["runs"][0]["results"][0]["ruleId"], "a\"}{");
+        assert_eq!(decoded["runs"][1]["automationDetails"]["id"], "custom/");
+        assert_eq!(decoded["runs"][2]["automationDetails"]["id"], "secrets/");
+        assert!(decoded["properties"]["runs"][0]
+            .get("automationDetails")
+            .is_none());
+    }
+
+    #[test]
+    fn test_encode_sarif_invalid_json_with_category() {
+        assert!(encode_sarif(&b"not json"[..], Some("secrets")).is_err());
+        assert!(encode_sarif(&b"{\"runs\":["[..], Some("secrets")).is_err());
+    }
+
+    #[test]
+    fn test_encode_sarif_too_large() {
+        let noise: Vec<u8> = (0..MAX_COMPRESSED_SIZE + 1024)
+            .map(|_| fastrand::u8(..))
+            .collect();
+        let err = encode_sarif(noise.as_slice(), None).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::Error::GitHub(GitHubError::SarifRejected(_))
+        ));
+    }
+}
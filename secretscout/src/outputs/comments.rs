@@ -2,14 +2,29 @@
 
 use crate::config::Config;
 use crate::error::Result;
-use crate::events::EventContext;
+use crate::events::{EventContext, PullRequest, Repository};
+use crate::github::client::GitHubClient;
 use crate::github::{self, PRComment};
+use crate::outputs::mentions::Mentions;
+use crate::outputs::summary;
 use crate::sarif::types::DetectedSecret;
+use std::collections::HashSet;
+
+/// Prefix of the reply posted on comments whose finding is gone
+const RESOLVED_REPLY_PREFIX: &str = "✅ Resolved in";
+
+/// Hidden marker identifying the sticky summary comment
+const SUMMARY_MARKER: &str = "<!-- secretscout:summary -->";
 
 /// Post PR comments for detected secrets
+///
+/// All new comments are submitted as a single review so reviewers get one
+/// notification. If GitHub rejects the batch (for example because a finding
+/// is outside the diff), the comments are posted one at a time instead.
 #[cfg(feature = "native")]
 pub async fn post_pr_comments(
     config: &Config,
+    client: &GitHubClient,
     context: &EventContext,
     findings: &[DetectedSecret],
 ) -> Result<usize> {
@@ -30,7 +45,7 @@ pub async fn post_pr_comments(
 
     // Fetch existing comments for deduplication
     let existing_comments =
-        match github::fetch_pr_comments(config, &context.repository, pr.number).await {
+        match github::fetch_pr_comments(config, client, &context.repository, pr.number).await {
             Ok(comments) => comments,
             Err(e) => {
                 log::warn!(
@@ -41,15 +56,17 @@ pub async fn post_pr_comments(
             }
         };
 
-    let mut posted = 0;
+    let mut mentions = Mentions::new(config, client, context).await;
+    let mut comments = Vec::new();
     let mut skipped = 0;
 
     for finding in findings {
+        let notify_users = mentions.for_file(&finding.file_path).await;
         let comment_body = github::build_comment_body(
             &finding.rule_id,
             &finding.commit_sha,
             &finding.fingerprint,
-            &config.notify_user_list,
+            &notify_users,
         );
 
         // Check for duplicates
@@ -68,29 +85,81 @@ pub async fn post_pr_comments(
             continue;
         }
 
-        let comment = PRComment {
+        comments.push(PRComment {
             body: comment_body,
             commit_id: finding.commit_sha.clone(),
             path: finding.file_path.clone(),
             line: finding.line_number,
             side: "RIGHT".to_string(),
-        };
+        });
+    }
+
+    if comments.is_empty() {
+        log::info!("No new PR comments to post, skipped {} duplicates", skipped);
+        return Ok(0);
+    }
+
+    let review_body = format!(
+        "🛑 **Gitleaks detected {} secret(s) in this pull request.**\n\n\
+         Each finding is commented on the affected line.",
+        comments.len()
+    );
+
+    let posted = match github::submit_pr_review(
+        config,
+        client,
+        &context.repository,
+        pr.number,
+        &pr.head.sha,
+        &review_body,
+        config.review_event,
+        &comments,
+    )
+    .await
+    {
+        Ok(()) => comments.len(),
+        Err(e) => {
+            log::warn!(
+                "Failed to submit review: {}. Falling back to individual comments.",
+                e
+            );
+            post_individual_comments(config, client, context, pr, &comments).await
+        }
+    };
 
+    log::info!(
+        "Posted {} PR comments, skipped {} duplicates",
+        posted,
+        skipped
+    );
+
+    Ok(posted)
+}
+
+/// Post comments one at a time, skipping any GitHub rejects
+#[cfg(feature = "native")]
+async fn post_individual_comments(
+    config: &Config,
+    client: &GitHubClient,
+    context: &EventContext,
+    pr: &PullRequest,
+    comments: &[PRComment],
+) -> usize {
+    let mut posted = 0;
+
+    for comment in comments {
         // Post comment (non-fatal errors)
-        match github::post_pr_comment(config, &context.repository, pr.number, &comment).await {
+        match github::post_pr_comment(config, client, &context.repository, pr.number, comment).await
+        {
             Ok(_) => {
-                log::debug!(
-                    "Posted comment on {}:{}",
-                    finding.file_path,
-                    finding.line_number
-                );
+                log::debug!("Posted comment on {}:{}", comment.path, comment.line);
                 posted += 1;
             }
             Err(e) => {
                 log::warn!(
                     "Failed to post comment on {}:{}: {}",
-                    finding.file_path,
-                    finding.line_number,
+                    comment.path,
+                    comment.line,
                     e
                 );
                 // Continue with other comments
@@ -98,17 +167,323 @@ pub async fn post_pr_comments(
         }
     }
 
-    log::info!(
-        "Posted {} PR comments, skipped {} duplicates",
-        posted,
-        skipped
-    );
+    posted
+}
 
-    Ok(posted)
+/// Mark SecretScout comments whose finding is no longer detected as outdated
+///
+/// Each stale comment gets a "resolved in <sha>" reply, and its review thread
+/// is resolved. If the thread cannot be found the comment is minimized
+/// instead. Findings acknowledged on the pull request are still in the code,
+/// so their comments are left alone. Returns the number of comments marked
+/// as outdated.
+#[cfg(feature = "native")]
+pub async fn resolve_stale_comments(
+    config: &Config,
+    client: &GitHubClient,
+    context: &EventContext,
+    findings: &[DetectedSecret],
+    acknowledged: &HashSet<String>,
+) -> Result<usize> {
+    let pr = match &context.pull_request {
+        Some(pr) => pr,
+        None => return Ok(0),
+    };
+
+    let existing_comments =
+        github::fetch_pr_comments(config, client, &context.repository, pr.number).await?;
+    let stale = find_stale_comments(&existing_comments, findings, acknowledged);
+
+    if stale.is_empty() {
+        log::debug!("No stale PR comments to resolve");
+        return Ok(0);
+    }
+
+    log::info!("Resolving {} stale PR comments", stale.len());
+
+    let threads =
+        match github::fetch_review_threads(config, client, &context.repository, pr.number).await {
+            Ok(threads) => threads,
+            Err(e) => {
+                log::warn!(
+                    "Failed to fetch review threads: {}. Minimizing comments instead.",
+                    e
+                );
+                Vec::new()
+            }
+        };
+
+    let reply = format!("{} {}", RESOLVED_REPLY_PREFIX, pr.head.sha);
+    let mut resolved = 0;
+
+    for comment in stale {
+        let comment_id = comment["id"].as_u64().unwrap_or_default();
+
+        if let Err(e) = github::reply_to_pr_comment(
+            config,
+            client,
+            &context.repository,
+            pr.number,
+            comment_id,
+            &reply,
+        )
+        .await
+        {
+            log::warn!("Failed to reply to comment {}: {}", comment_id, e);
+            continue;
+        }
+
+        let thread = threads.iter().find(|t| t.comment_ids.contains(&comment_id));
+
+        let result = match thread {
+            Some(thread) if thread.is_resolved => Ok(()),
+            Some(thread) => github::resolve_review_thread(config, client, &thread.id).await,
+            None => {
+                let node_id = comment["node_id"].as_str().unwrap_or_default();
+                github::minimize_comment(config, client, node_id).await
+            }
+        };
+
+        match result {
+            Ok(()) => resolved += 1,
+            Err(e) => log::warn!("Failed to resolve comment {}: {}", comment_id, e),
+        }
+    }
+
+    log::info!("Marked {} PR comments as outdated", resolved);
+
+    Ok(resolved)
+}
+
+/// Create or update the sticky summary comment on the pull request
+///
+/// The comment is edited in place on every run. Once the findings are gone it
+/// switches to an all-clear message; a PR that never had findings gets no
+/// summary comment at all. `findings_count` also counts findings left out of
+/// `findings`.
+#[cfg(feature = "native")]
+pub async fn upsert_summary_comment(
+    config: &Config,
+    client: &GitHubClient,
+    context: &EventContext,
+    findings: &[DetectedSecret],
+    findings_count: usize,
+) -> Result<()> {
+    let pr = match &context.pull_request {
+        Some(pr) => pr,
+        None => return Ok(()),
+    };
+    let pr_number = pr.number as u64;
+
+    let existing =
+        github::fetch_issue_comments(config, client, &context.repository, pr_number).await?;
+    let body = build_summary_comment(&context.repository, findings, findings_count, &pr.head.sha);
+
+    match find_summary_comment(&existing) {
+        Some(comment) if comment["body"].as_str() == Some(body.as_str()) => {
+            log::debug!("Summary comment is up to date");
+        }
+        Some(comment) => {
+            let comment_id = comment["id"].as_u64().unwrap_or_default();
+            log::info!("Updating summary comment {}", comment_id);
+            github::update_issue_comment(config, client, &context.repository, comment_id, &body)
+                .await?;
+        }
+        None if findings.is_empty() => {
+            log::debug!("No findings and no summary comment, nothing to post");
+        }
+        None => {
+            log::info!("Posting summary comment on PR #{}", pr.number);
+            github::create_issue_comment(config, client, &context.repository, pr_number, &body)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Build the body of the sticky summary comment
+pub fn build_summary_comment(
+    repository: &Repository,
+    findings: &[DetectedSecret],
+    findings_count: usize,
+    head_sha: &str,
+) -> String {
+    let content = if findings.is_empty() {
+        format!(
+            "{}\nAll previously reported secrets are gone as of {}.\n",
+            summary::generate_success_summary(),
+            head_sha
+        )
+    } else {
+        let mut table = summary::generate_findings_summary(repository, findings);
+        if findings.len() < findings_count {
+            table.push_str(&summary::generate_truncation_note(
+                findings.len(),
+                findings_count,
+            ));
+        }
+        format!("{}\nLast updated for {}.\n", table, head_sha)
+    };
+
+    format!("{}\n{}", SUMMARY_MARKER, content)
+}
+
+/// Find the sticky summary comment among the PR's comments
+///
+/// Only comments by bots count, so a user quoting the marker cannot take the
+/// place of the summary.
+pub fn find_summary_comment(comments: &[serde_json::Value]) -> Option<&serde_json::Value> {
+    comments.iter().filter(|c| is_by_bot(c)).find(|c| {
+        c["body"]
+            .as_str()
+            .is_some_and(|body| body.starts_with(SUMMARY_MARKER))
+    })
+}
+
+/// Find SecretScout comments whose fingerprint is no longer detected
+///
+/// Only comments and "resolved in" replies by bots count, so users quoting
+/// them can neither get their comments resolved nor suppress the bot.
+/// Replies, comments that already carry a "resolved in" reply and comments
+/// on acknowledged findings are skipped.
+pub fn find_stale_comments<'a>(
+    existing_comments: &'a [serde_json::Value],
+    findings: &[DetectedSecret],
+    acknowledged: &HashSet<String>,
+) -> Vec<&'a serde_json::Value> {
+    let current: HashSet<&str> = findings.iter().map(|f| f.fingerprint.as_str()).collect();
+
+    let already_resolved: HashSet<u64> = existing_comments
+        .iter()
+        .filter(|c| is_by_bot(c))
+        .filter(|c| {
+            c["body"]
+                .as_str()
+                .is_some_and(|b| b.starts_with(RESOLVED_REPLY_PREFIX))
+        })
+        .filter_map(|c| c["in_reply_to_id"].as_u64())
+        .collect();
+
+    existing_comments
+        .iter()
+        .filter(|c| is_by_bot(c))
+        .filter(|c| c["in_reply_to_id"].is_null())
+        .filter(|c| {
+            c["id"]
+                .as_u64()
+                .is_some_and(|id| !already_resolved.contains(&id))
+        })
+        .filter(|c| {
+            c["body"]
+                .as_str()
+                .and_then(github::extract_fingerprint)
+                .is_some_and(|fingerprint| {
+                    !current.contains(fingerprint) && !acknowledged.contains(fingerprint)
+                })
+        })
+        .collect()
+}
+
+/// Whether a comment was posted by a bot, as SecretScout's comments are
+fn is_by_bot(comment: &serde_json::Value) -> bool {
+    comment["user"]["type"].as_str() == Some("Bot")
 }
 
 #[cfg(test)]
 mod tests {
-    // Mock tests would go here - they require complex setup with API mocking
-    // For now, the integration will be tested at a higher level
+    use super::*;
+
+    fn finding(fingerprint: &str) -> DetectedSecret {
+        DetectedSecret {
+            rule_id: "aws-access-token".to_string(),
+            file_path: "src/main.rs".to_string(),
+            line_number: 42,
+            commit_sha: "abc123".to_string(),
+            author: "Jane".to_string(),
+            email: "jane@example.com".to_string(),
+            date: "2025-10-16".to_string(),
+            fingerprint: fingerprint.to_string(),
+            owners: Vec::new(),
+        }
+    }
+
+    fn comment(id: u64, fingerprint: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "body": github::build_comment_body("aws-access-token", "abc123", fingerprint, &[]),
+            "in_reply_to_id": null,
+            "user": { "login": "github-actions[bot]", "type": "Bot" },
+        })
+    }
+
+    #[test]
+    fn test_find_stale_comments() {
+        let user = serde_json::json!({ "login": "someone", "type": "User" });
+        let bot = serde_json::json!({ "login": "github-actions[bot]", "type": "Bot" });
+        let existing = vec![
+            comment(1, "abc123:src/main.rs:aws-access-token:42"),
+            comment(2, "abc123:src/old.rs:aws-access-token:7"),
+            comment(3, "abc123:src/gone.rs:aws-access-token:9"),
+            serde_json::json!({ "id": 4, "body": "✅ Resolved in def456", "in_reply_to_id": 3, "user": bot }),
+            serde_json::json!({ "id": 5, "body": "Please fix", "in_reply_to_id": null, "user": user }),
+            comment(6, "abc123:src/acked.rs:aws-access-token:1"),
+        ];
+        let findings = vec![finding("abc123:src/main.rs:aws-access-token:42")];
+        let acknowledged = HashSet::from(["abc123:src/acked.rs:aws-access-token:1".to_string()]);
+
+        let stale = find_stale_comments(&existing, &findings, &acknowledged);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0]["id"], 2);
+    }
+
+    #[test]
+    fn test_find_stale_comments_ignores_users() {
+        let user = serde_json::json!({ "login": "someone", "type": "User" });
+        let mut quote = comment(1, "abc123:src/old.rs:aws-access-token:7");
+        quote["user"] = user.clone();
+        let existing = vec![
+            quote,
+            comment(2, "abc123:src/gone.rs:aws-access-token:9"),
+            serde_json::json!({ "id": 3, "body": "✅ Resolved in def456", "in_reply_to_id": 2, "user": user }),
+        ];
+
+        // A user quoting the bot is not resolved, and a user's "resolved"
+        // reply does not stop the bot from resolving its own comment
+        let stale = find_stale_comments(&existing, &[], &HashSet::new());
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0]["id"], 2);
+    }
+
+    #[test]
+    fn test_summary_comment() {
+        let repository = Repository {
+            owner: "owner".to_string(),
+            name: "repo".to_string(),
+            full_name: "owner/repo".to_string(),
+            html_url: "https://github.com/owner/repo".to_string(),
+        };
+
+        let leaks = build_summary_comment(&repository, &[finding("fp")], 1, "def456");
+        assert!(leaks.starts_with(SUMMARY_MARKER));
+        assert!(leaks.contains("Gitleaks detected secrets"));
+        assert!(leaks.contains("Last updated for def456"));
+        assert!(!leaks.contains("Showing the first"));
+
+        let truncated = build_summary_comment(&repository, &[finding("fp")], 1500, "def456");
+        assert!(truncated.contains("Showing the first 1 of 1500 findings"));
+
+        let clear = build_summary_comment(&repository, &[], 0, "def456");
+        assert!(clear.contains("No leaks detected"));
+
+        let bot = serde_json::json!({ "login": "github-actions[bot]", "type": "Bot" });
+        let user = serde_json::json!({ "login": "someone", "type": "User" });
+        let comments = vec![
+            serde_json::json!({ "id": 1, "body": "LGTM", "user": user }),
+            serde_json::json!({ "id": 2, "body": clear, "user": user }),
+            serde_json::json!({ "id": 3, "body": clear, "user": bot }),
+        ];
+        assert_eq!(find_summary_comment(&comments).unwrap()["id"], 3);
+        assert!(find_summary_comment(&comments[..2]).is_none());
+    }
 }
@@ -0,0 +1,83 @@
+//! Commit status module
+//!
+//! Reports the scan result as a `secretscout/scan` commit status. Statuses
+//! only need a token that can write statuses, so they also gate merges when
+//! the scan runs in a CI system other than GitHub Actions.
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::events::Repository;
+use crate::github::client::GitHubClient;
+use crate::github::{self, CommitState, CommitStatus};
+
+/// Context of the commit status, as required in branch protection
+pub const STATUS_CONTEXT: &str = "secretscout/scan";
+
+/// Result of a scan as reported in the commit status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanOutcome {
+    /// Scan completed without findings
+    Clean,
+    /// Scan detected secrets; the count is unknown for unreadable reports
+    Leaks(Option<usize>),
+    /// Scan could not complete
+    Failed,
+}
+
+/// Set the commit status for a scan of `sha`
+#[cfg(feature = "native")]
+pub async fn publish_commit_status(
+    config: &Config,
+    client: &GitHubClient,
+    repository: &Repository,
+    sha: &str,
+    outcome: ScanOutcome,
+    target_url: Option<&str>,
+) -> Result<()> {
+    let status = build_commit_status(outcome, target_url);
+    github::create_commit_status(config, client, repository, sha, &status).await
+}
+
+/// Build the commit status for a scan outcome
+pub fn build_commit_status(outcome: ScanOutcome, target_url: Option<&str>) -> CommitStatus {
+    let (state, description) = match outcome {
+        ScanOutcome::Clean => (CommitState::Success, "No secrets found".to_string()),
+        ScanOutcome::Leaks(Some(1)) => (CommitState::Failure, "1 secret found".to_string()),
+        ScanOutcome::Leaks(Some(count)) => {
+            (CommitState::Failure, format!("{} secrets found", count))
+        }
+        ScanOutcome::Leaks(None) => (CommitState::Failure, "Secrets found".to_string()),
+        ScanOutcome::Failed => (CommitState::Error, "Secret scan failed".to_string()),
+    };
+
+    CommitStatus {
+        state,
+        target_url: target_url.map(str::to_string),
+        description,
+        context: STATUS_CONTEXT.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_commit_status() {
+        let status = build_commit_status(ScanOutcome::Clean, None);
+        assert_eq!(status.state, CommitState::Success);
+        assert_eq!(status.context, "secretscout/scan");
+        assert!(status.target_url.is_none());
+
+        let status = build_commit_status(ScanOutcome::Leaks(Some(3)), Some("https://ci/job/1"));
+        assert_eq!(status.state, CommitState::Failure);
+        assert_eq!(status.description, "3 secrets found");
+        assert_eq!(status.target_url.as_deref(), Some("https://ci/job/1"));
+
+        let status = build_commit_status(ScanOutcome::Leaks(Some(1)), None);
+        assert_eq!(status.description, "1 secret found");
+
+        let status = build_commit_status(ScanOutcome::Failed, None);
+        assert_eq!(status.state, CommitState::Error);
+    }
+}
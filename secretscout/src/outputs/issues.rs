@@ -0,0 +1,514 @@
+//! Issue tracking module
+//!
+//! Tracks findings of push, schedule and workflow_dispatch scans as GitHub
+//! issues, either one per finding or one rollup issue per rule. Each issue
+//! carries a hidden marker so later scans find it again:
+//!
+//! - open issues are left alone (rollups get new findings added),
+//! - issues SecretScout closed are reopened when the finding comes back,
+//! - issues closed as "not planned" are treated as dismissed and stay closed.
+//!
+//! Issues are closed automatically only after full scans. Push scans cover
+//! just the pushed commits, so a missing finding there proves nothing.
+
+use crate::codeowners::CodeOwners;
+use crate::config::{Config, IssueMode};
+use crate::error::Result;
+use crate::events::{self, EventContext};
+use crate::github;
+use crate::github::client::GitHubClient;
+use crate::sarif::types::DetectedSecret;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Label that marks issues managed by SecretScout
+pub const ISSUE_LABEL: &str = "secretscout";
+
+/// Maximum number of assignees GitHub accepts per issue
+const MAX_ASSIGNEES: usize = 10;
+
+/// Changes made while syncing issues
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IssueSync {
+    pub opened: usize,
+    pub reopened: usize,
+    pub updated: usize,
+    pub closed: usize,
+}
+
+/// Change made to the issue of a finding or rule
+enum Change {
+    Unchanged,
+    Updated,
+    Reopened,
+    Opened,
+}
+
+/// Existing issue that carries a SecretScout marker
+#[derive(Debug)]
+struct TrackedIssue {
+    number: u64,
+    open: bool,
+    dismissed: bool,
+    body: String,
+}
+
+/// Open, update, reopen and close tracking issues for the scan's findings
+///
+/// `complete` is false when `findings` leaves out some of the scan's
+/// findings; no issue is closed then.
+#[cfg(feature = "native")]
+pub async fn sync_issues(
+    config: &Config,
+    client: &GitHubClient,
+    context: &EventContext,
+    findings: &[DetectedSecret],
+    complete: bool,
+) -> Result<IssueSync> {
+    let kind = match config.issue_mode {
+        IssueMode::Off => return Ok(IssueSync::default()),
+        IssueMode::PerFinding => "finding",
+        IssueMode::PerRule => "rule",
+    };
+
+    let full_scan = events::build_log_opts(context).is_empty();
+    let repository = &context.repository;
+
+    let existing = github::fetch_issues(config, client, repository, ISSUE_LABEL).await?;
+    let tracked = find_tracked_issues(&existing, kind);
+    let groups = group_findings(config.issue_mode, findings);
+
+    let mut labels = vec![ISSUE_LABEL.to_string()];
+    labels.extend(config.issue_labels.iter().cloned());
+
+    let codeowners = CodeOwners::for_event(config, context).await;
+    let mut logins = HashMap::new();
+    let mut sync = IssueSync::default();
+
+    for (key, group) in &groups {
+        let body = match tracked.get(key.as_str()) {
+            Some(issue) if config.issue_mode == IssueMode::PerRule && !full_scan => {
+                merge_rollup_body(&issue.body, group)
+            }
+            _ => build_issue_body(config.issue_mode, repository, key, group),
+        };
+
+        let result = match tracked.get(key.as_str()) {
+            Some(issue) if issue.dismissed => {
+                log::debug!("Issue #{} was dismissed, leaving it closed", issue.number);
+                Ok(Change::Unchanged)
+            }
+            Some(issue) if issue.open => {
+                if config.issue_mode == IssueMode::PerFinding || issue.body == body {
+                    Ok(Change::Unchanged)
+                } else {
+                    github::update_issue(
+                        config,
+                        client,
+                        repository,
+                        issue.number,
+                        &serde_json::json!({ "body": body }),
+                    )
+                    .await
+                    .map(|_| Change::Updated)
+                }
+            }
+            Some(issue) => reopen_issue(config, client, context, issue.number, &body)
+                .await
+                .map(|_| Change::Reopened),
+            None => {
+                let assignees = resolve_assignees(
+                    config,
+                    client,
+                    context,
+                    codeowners.as_ref(),
+                    group,
+                    &mut logins,
+                )
+                .await;
+                github::create_issue(
+                    config,
+                    client,
+                    repository,
+                    &build_issue_title(config.issue_mode, key, group),
+                    &body,
+                    &labels,
+                    &assignees,
+                )
+                .await
+                .map(|_| Change::Opened)
+            }
+        };
+
+        match result {
+            Ok(Change::Unchanged) => {}
+            Ok(Change::Updated) => sync.updated += 1,
+            Ok(Change::Reopened) => sync.reopened += 1,
+            Ok(Change::Opened) => sync.opened += 1,
+            Err(e) => log::warn!("Failed to track finding {} as an issue: {}", key, e),
+        }
+    }
+
+    if full_scan && complete {
+        for (key, issue) in &tracked {
+            if !issue.open || groups.contains_key(*key) {
+                continue;
+            }
+
+            match close_issue(config, client, context, issue.number).await {
+                Ok(()) => sync.closed += 1,
+                Err(e) => log::warn!("Failed to close issue #{}: {}", issue.number, e),
+            }
+        }
+    }
+
+    Ok(sync)
+}
+
+#[cfg(feature = "native")]
+async fn reopen_issue(
+    config: &Config,
+    client: &GitHubClient,
+    context: &EventContext,
+    issue_number: u64,
+    body: &str,
+) -> Result<()> {
+    github::update_issue(
+        config,
+        client,
+        &context.repository,
+        issue_number,
+        &serde_json::json!({ "state": "open", "body": body }),
+    )
+    .await?;
+
+    github::create_issue_comment(
+        config,
+        client,
+        &context.repository,
+        issue_number,
+        &format!("🛑 Detected again in {}", config.sha),
+    )
+    .await
+}
+
+#[cfg(feature = "native")]
+async fn close_issue(
+    config: &Config,
+    client: &GitHubClient,
+    context: &EventContext,
+    issue_number: u64,
+) -> Result<()> {
+    github::create_issue_comment(
+        config,
+        client,
+        &context.repository,
+        issue_number,
+        &format!("✅ No longer detected as of {}", config.sha),
+    )
+    .await?;
+
+    github::update_issue(
+        config,
+        client,
+        &context.repository,
+        issue_number,
+        &serde_json::json!({ "state": "closed", "state_reason": "completed" }),
+    )
+    .await
+}
+
+/// Assign code owners of the affected files, or the commit authors for
+/// files without owners
+///
+/// Teams and email owners cannot be assigned and are skipped.
+#[cfg(feature = "native")]
+async fn resolve_assignees(
+    config: &Config,
+    client: &GitHubClient,
+    context: &EventContext,
+    codeowners: Option<&CodeOwners>,
+    findings: &[&DetectedSecret],
+    logins: &mut HashMap<String, Option<String>>,
+) -> Vec<String> {
+    let mut assignees: Vec<String> = Vec::new();
+
+    for finding in findings {
+        let owners: Vec<String> = codeowners
+            .map(|c| c.owners_of(&finding.file_path))
+            .unwrap_or(&[])
+            .iter()
+            .filter_map(|owner| owner.strip_prefix('@'))
+            .filter(|owner| !owner.contains('/'))
+            .map(str::to_string)
+            .collect();
+
+        let candidates = if owners.is_empty() {
+            if !logins.contains_key(&finding.commit_sha) {
+                let login = github::fetch_commit_author_login(
+                    config,
+                    client,
+                    &context.repository,
+                    &finding.commit_sha,
+                )
+                .await
+                .unwrap_or_else(|e| {
+                    log::debug!("Failed to look up author of {}: {}", finding.commit_sha, e);
+                    None
+                });
+                logins.insert(finding.commit_sha.clone(), login);
+            }
+            logins[&finding.commit_sha].iter().cloned().collect()
+        } else {
+            owners
+        };
+
+        for login in candidates {
+            if !assignees.contains(&login) {
+                assignees.push(login);
+            }
+        }
+    }
+
+    assignees.truncate(MAX_ASSIGNEES);
+    assignees
+}
+
+/// Index issues by the finding or rule in their marker
+///
+/// When several issues share a marker, the open one wins.
+fn find_tracked_issues<'a>(
+    issues: &'a [serde_json::Value],
+    kind: &str,
+) -> HashMap<&'a str, TrackedIssue> {
+    let mut tracked: HashMap<&str, TrackedIssue> = HashMap::new();
+
+    for issue in issues {
+        let body = issue["body"].as_str().unwrap_or_default();
+        let (Some(key), Some(number)) = (extract_marker(body, kind), issue["number"].as_u64())
+        else {
+            continue;
+        };
+
+        let open = issue["state"].as_str() == Some("open");
+        if tracked.get(key).is_some_and(|t| t.open) {
+            continue;
+        }
+
+        tracked.insert(
+            key,
+            TrackedIssue {
+                number,
+                open,
+                dismissed: !open && issue["state_reason"].as_str() == Some("not_planned"),
+                body: body.to_string(),
+            },
+        );
+    }
+
+    tracked
+}
+
+/// Group findings by fingerprint or by rule, depending on the mode
+fn group_findings(
+    mode: IssueMode,
+    findings: &[DetectedSecret],
+) -> BTreeMap<String, Vec<&DetectedSecret>> {
+    let mut groups: BTreeMap<String, Vec<&DetectedSecret>> = BTreeMap::new();
+
+    for finding in findings {
+        let key = match mode {
+            IssueMode::PerRule => &finding.rule_id,
+            _ => &finding.fingerprint,
+        };
+        groups.entry(key.clone()).or_default().push(finding);
+    }
+
+    groups
+}
+
+/// Hidden marker that identifies the finding or rule of an issue
+fn marker(kind: &str, key: &str) -> String {
+    format!("<!-- secretscout:{} {} -->", kind, key)
+}
+
+/// Extract the key of a marker written by [`marker`]
+fn extract_marker<'a>(body: &'a str, kind: &str) -> Option<&'a str> {
+    let prefix = format!("<!-- secretscout:{} ", kind);
+    let start = body.find(&prefix)? + prefix.len();
+    let end = body[start..].find(" -->")?;
+    Some(&body[start..start + end])
+}
+
+fn build_issue_title(mode: IssueMode, key: &str, findings: &[&DetectedSecret]) -> String {
+    match (mode, findings.first()) {
+        (IssueMode::PerRule, _) => format!("Secrets detected: {}", key),
+        (_, Some(finding)) => format!(
+            "Secret detected: {} in {}",
+            finding.rule_id, finding.file_path
+        ),
+        (_, None) => format!("Secret detected: {}", key),
+    }
+}
+
+fn build_issue_body(
+    mode: IssueMode,
+    repository: &events::Repository,
+    key: &str,
+    findings: &[&DetectedSecret],
+) -> String {
+    let kind = if mode == IssueMode::PerRule {
+        "rule"
+    } else {
+        "finding"
+    };
+
+    let mut body = format!(
+        "{}\n🛑 **Gitleaks detected `{}` in `{}`**\n\n\
+         Rotate the secret and remove it from the code. If this is a false \
+         positive, add the fingerprint to `.gitleaksignore` and close this issue \
+         as not planned.\n\n\
+         _This issue is closed automatically once a full scan no longer reports it._\n\n\
+         | File | Commit | Author | Fingerprint |\n\
+         |------|--------|--------|-------------|\n",
+        marker(kind, key),
+        findings.first().map_or(key, |f| f.rule_id.as_str()),
+        repository.full_name
+    );
+
+    for finding in findings {
+        body.push_str(&build_row(repository, finding));
+    }
+
+    body
+}
+
+/// Append rows for findings a rollup issue does not list yet
+fn merge_rollup_body(body: &str, findings: &[&DetectedSecret]) -> String {
+    let mut merged = body.trim_end().to_string();
+    merged.push('\n');
+
+    let known: HashSet<&str> = findings
+        .iter()
+        .map(|f| f.fingerprint.as_str())
+        .filter(|fp| body.contains(&format!("`{}`", fp)))
+        .collect();
+
+    for finding in findings {
+        if !known.contains(finding.fingerprint.as_str()) {
+            merged.push_str(&build_row_without_links(finding));
+        }
+    }
+
+    merged
+}
+
+fn build_row(repository: &events::Repository, finding: &DetectedSecret) -> String {
+    format!(
+        "| [`{}:{}`]({}) | [`{}`]({}) | {} | `{}` |\n",
+        cell(&finding.file_path),
+        finding.line_number,
+        finding.secret_url(&repository.html_url),
+        finding.short_sha(),
+        finding.commit_url(&repository.html_url),
+        cell(&finding.author),
+        finding.fingerprint
+    )
+}
+
+fn build_row_without_links(finding: &DetectedSecret) -> String {
+    format!(
+        "| `{}:{}` | `{}` | {} | `{}` |\n",
+        cell(&finding.file_path),
+        finding.line_number,
+        finding.short_sha(),
+        cell(&finding.author),
+        finding.fingerprint
+    )
+}
+
+/// Escape a value for a markdown table cell
+fn cell(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(rule_id: &str, file_path: &str) -> DetectedSecret {
+        DetectedSecret {
+            rule_id: rule_id.to_string(),
+            file_path: file_path.to_string(),
+            line_number: 42,
+            commit_sha: "abc123def456".to_string(),
+            author: "Jane".to_string(),
+            email: "jane@example.com".to_string(),
+            date: "2025-10-16".to_string(),
+            fingerprint: format!("abc123def456:{}:{}:42", file_path, rule_id),
+            owners: Vec::new(),
+        }
+    }
+
+    fn issue(number: u64, state: &str, reason: Option<&str>, body: &str) -> serde_json::Value {
+        serde_json::json!({
+            "number": number,
+            "state": state,
+            "state_reason": reason,
+            "body": body,
+        })
+    }
+
+    #[test]
+    fn test_find_tracked_issues() {
+        let issues = vec![
+            issue(1, "closed", Some("completed"), &marker("finding", "fp-a")),
+            issue(2, "open", None, &marker("finding", "fp-a")),
+            issue(3, "closed", Some("not_planned"), &marker("finding", "fp-b")),
+            issue(4, "open", None, &marker("rule", "aws-access-token")),
+            issue(5, "open", None, "Unrelated issue"),
+        ];
+
+        let tracked = find_tracked_issues(&issues, "finding");
+        assert_eq!(tracked.len(), 2);
+        assert_eq!(tracked["fp-a"].number, 2);
+        assert!(tracked["fp-b"].dismissed);
+    }
+
+    #[test]
+    fn test_group_findings() {
+        let findings = vec![
+            finding("aws-access-token", "a.rs"),
+            finding("aws-access-token", "b.rs"),
+            finding("github-pat", "a.rs"),
+        ];
+
+        assert_eq!(group_findings(IssueMode::PerFinding, &findings).len(), 3);
+
+        let groups = group_findings(IssueMode::PerRule, &findings);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["aws-access-token"].len(), 2);
+    }
+
+    #[test]
+    fn test_merge_rollup_body() {
+        let repository = events::Repository {
+            owner: "owner".to_string(),
+            name: "repo".to_string(),
+            full_name: "owner/repo".to_string(),
+            html_url: "https://github.com/owner/repo".to_string(),
+        };
+        let old = finding("aws-access-token", "a.rs");
+        let new = finding("aws-access-token", "b.rs");
+
+        let body = build_issue_body(IssueMode::PerRule, &repository, "aws-access-token", &[&old]);
+        assert_eq!(extract_marker(&body, "rule"), Some("aws-access-token"));
+        assert!(body.contains("https://github.com/owner/repo/blob/abc123def456/a.rs#L42"));
+
+        let merged = merge_rollup_body(&body, &[&old, &new]);
+        assert!(merged.starts_with(body.trim_end()));
+        assert_eq!(merged.matches(&old.fingerprint).count(), 1);
+        assert!(merged.contains(&new.fingerprint));
+
+        assert_eq!(merge_rollup_body(&merged, &[&new]), merged);
+    }
+}
@@ -0,0 +1,224 @@
+//! Mention resolution module
+//!
+//! Decides whom a PR comment mentions. Every user and team is checked through
+//! the API once per run, so a typo in the notify list is reported and dropped
+//! instead of silently pinging nobody.
+
+use crate::codeowners::CodeOwners;
+use crate::config::Config;
+use crate::error::{Error, GitHubError, Result};
+use crate::events::EventContext;
+use crate::github::client::GitHubClient;
+use crate::github::{self, AccountType};
+use std::collections::HashMap;
+
+/// A GitHub user or team that can be mentioned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mention {
+    User(String),
+    Team { org: String, slug: String },
+}
+
+impl Mention {
+    /// Parse `@user`, `user` or `@org/team`
+    ///
+    /// Returns `None` for entries that cannot be a GitHub login, such as
+    /// emails or names with invalid characters.
+    pub fn parse(entry: &str) -> Option<Self> {
+        let name = entry.trim().trim_start_matches('@');
+
+        match name.split_once('/') {
+            Some((org, slug)) if is_login(org) && is_team_slug(slug) => Some(Mention::Team {
+                org: org.to_string(),
+                slug: slug.to_string(),
+            }),
+            Some(_) => None,
+            None if is_login(name) => Some(Mention::User(name.to_string())),
+            None => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Mention {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mention::User(login) => write!(f, "@{}", login),
+            Mention::Team { org, slug } => write!(f, "@{}/{}", org, slug),
+        }
+    }
+}
+
+/// Resolves the validated mentions for each finding
+pub struct Mentions<'a> {
+    config: &'a Config,
+    client: &'a GitHubClient,
+    code_owners: Option<CodeOwners>,
+    checked: HashMap<String, bool>,
+}
+
+impl<'a> Mentions<'a> {
+    /// Create a resolver, loading CODEOWNERS when `notify_code_owners` is set
+    ///
+    /// CODEOWNERS is read as [`CodeOwners::for_event`] does, so a pull
+    /// request cannot choose whom a base branch token run mentions.
+    pub async fn new(config: &'a Config, client: &'a GitHubClient, context: &EventContext) -> Self {
+        let code_owners = if config.notify_code_owners {
+            let owners = CodeOwners::for_event(config, context).await;
+            if owners.is_none() {
+                log::warn!("notify_code_owners is set but the repository has no CODEOWNERS file");
+            }
+            owners
+        } else {
+            None
+        };
+
+        Self {
+            config,
+            client,
+            code_owners,
+            checked: HashMap::new(),
+        }
+    }
+
+    /// Mentions for a finding in `path`
+    ///
+    /// The file's code owners when CODEOWNERS notification is enabled and the
+    /// file has owners, otherwise the notify list. Invalid entries are dropped.
+    pub async fn for_file(&mut self, path: &str) -> Vec<String> {
+        // Code owners given as emails cannot be mentioned
+        let owners: Vec<String> = self
+            .code_owners
+            .as_ref()
+            .map(|owners| owners.owners_of(path))
+            .unwrap_or_default()
+            .iter()
+            .filter(|owner| owner.starts_with('@'))
+            .cloned()
+            .collect();
+
+        let candidates = if owners.is_empty() {
+            self.config.notify_user_list.clone()
+        } else {
+            owners
+        };
+
+        let mut mentions = Vec::new();
+        for candidate in &candidates {
+            if let Some(mention) = self.validate(candidate).await {
+                if !mentions.contains(&mention) {
+                    mentions.push(mention);
+                }
+            }
+        }
+        mentions
+    }
+
+    /// Validate an entry, caching the result for the rest of the run
+    async fn validate(&mut self, entry: &str) -> Option<String> {
+        let Some(mention) = Mention::parse(entry) else {
+            log::warn!("Dropping '{}': not a GitHub user or team", entry);
+            return None;
+        };
+        let mention_text = mention.to_string();
+
+        if let Some(&valid) = self.checked.get(&mention_text) {
+            return valid.then_some(mention_text);
+        }
+
+        let valid = match check_mention(self.config, self.client, &mention).await {
+            Ok(valid) => valid,
+            Err(e) => {
+                // Keep mentions the API could not check rather than lose them
+                log::warn!("Could not verify {}: {}", mention_text, e);
+                true
+            }
+        };
+
+        self.checked.insert(mention_text.clone(), valid);
+        valid.then_some(mention_text)
+    }
+}
+
+/// Check that a user or team exists and can be mentioned
+async fn check_mention(config: &Config, client: &GitHubClient, mention: &Mention) -> Result<bool> {
+    match mention {
+        Mention::User(login) => match github::fetch_account_info(config, client, login).await {
+            Ok(info) if info.account_type == AccountType::User => Ok(true),
+            Ok(_) => {
+                log::warn!(
+                    "Dropping mention {}: organizations cannot be notified",
+                    mention
+                );
+                Ok(false)
+            }
+            Err(Error::GitHub(GitHubError::NotFound(_))) => {
+                log::warn!("Dropping mention {}: no such GitHub user", mention);
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        },
+        Mention::Team { org, slug } => {
+            let exists = github::team_exists(config, client, org, slug).await?;
+            if !exists {
+                log::warn!("Dropping mention {}: no such team", mention);
+            }
+            Ok(exists)
+        }
+    }
+}
+
+/// GitHub login: alphanumerics and single inner hyphens, up to 39 characters
+fn is_login(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 39
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && !name.contains("--")
+}
+
+/// Team slug: alphanumerics, hyphens and underscores
+fn is_team_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mention() {
+        assert_eq!(
+            Mention::parse("@octocat"),
+            Some(Mention::User("octocat".to_string()))
+        );
+        assert_eq!(
+            Mention::parse(" octo-cat "),
+            Some(Mention::User("octo-cat".to_string()))
+        );
+        assert_eq!(
+            Mention::parse("@acme/security_team"),
+            Some(Mention::Team {
+                org: "acme".to_string(),
+                slug: "security_team".to_string()
+            })
+        );
+
+        assert_eq!(Mention::parse("jane@example.com"), None);
+        assert_eq!(Mention::parse("@-octocat"), None);
+        assert_eq!(Mention::parse("@a/b/c"), None);
+        assert_eq!(Mention::parse("@"), None);
+    }
+
+    #[test]
+    fn test_mention_display() {
+        assert_eq!(Mention::parse("octocat").unwrap().to_string(), "@octocat");
+        assert_eq!(
+            Mention::parse("@acme/sec").unwrap().to_string(),
+            "@acme/sec"
+        );
+    }
+}
@@ -3,10 +3,27 @@
 //! This module handles all output generation including job summaries,
 //! PR comments, and artifact handling.
 
+pub mod artifact;
+pub mod check_run;
+pub mod code_scanning;
 pub mod comments;
+pub mod commit_status;
+pub mod issues;
+pub mod mentions;
 pub mod summary;
+pub mod workflow_commands;
 
-pub use comments::post_pr_comments;
+pub use artifact::upload_sarif_artifact;
+pub use check_run::{publish_check_run, CheckConclusion};
+pub use code_scanning::upload_to_code_scanning;
+pub use comments::{post_pr_comments, resolve_stale_comments, upsert_summary_comment};
+pub use commit_status::{publish_commit_status, ScanOutcome};
+pub use issues::sync_issues;
 pub use summary::{
-    generate_error_summary, generate_findings_summary, generate_success_summary, write_summary,
+    generate_base_token_note, generate_error_summary, generate_findings_summary,
+    generate_success_summary, generate_truncation_note, generate_unconvertible_note, write_summary,
+};
+pub use workflow_commands::{
+    build_step_outputs, format_annotation, format_omitted_annotations, write_step_outputs,
+    MAX_ANNOTATIONS,
 };
@@ -10,6 +10,20 @@ pub fn generate_success_summary() -> String {
     "## No leaks detected ✅\n".to_string()
 }
 
+/// Generate the security note for pull request scans that run with the base
+/// repository's token (`pull_request_target` and `issue_comment`)
+pub fn generate_base_token_note(event_name: &str) -> String {
+    format!(
+        "> [!WARNING]\n\
+         > This scan ran on `{}`, in the context of the base repository and \
+         with its write token. SecretScout only read the pull request's commits \
+         and withheld all tokens from gitleaks. Do not build, test or otherwise \
+         run code from the pull request in this workflow: it would have access \
+         to the repository's secrets.\n",
+        event_name
+    )
+}
+
 /// Generate error summary (gitleaks failed)
 pub fn generate_error_summary(exit_code: i32) -> String {
     format!(
@@ -18,9 +32,26 @@ pub fn generate_error_summary(exit_code: i32) -> String {
     )
 }
 
+/// Generate the note for a findings summary that lists only some findings
+pub fn generate_truncation_note(shown: usize, total: usize) -> String {
+    format!(
+        "\n_Showing the first {} of {} findings. The SARIF report lists them all._\n",
+        shown, total
+    )
+}
+
+/// Generate the note for results gitleaks reported that could not be read
+pub fn generate_unconvertible_note(count: usize) -> String {
+    format!(
+        "\n_{} result(s) in the SARIF report lack a location or fingerprints and are not listed here._\n",
+        count
+    )
+}
+
 /// Generate findings summary with HTML table
 pub fn generate_findings_summary(repository: &Repository, findings: &[DetectedSecret]) -> String {
     let mut summary = String::from("## 🛑 Gitleaks detected secrets 🛑\n\n");
+    let show_owners = findings.iter().any(|f| !f.owners.is_empty());
 
     summary.push_str("<table>\n");
     summary.push_str("<tr>\n");
@@ -32,6 +63,9 @@ pub fn generate_findings_summary(repository: &Repository, findings: &[DetectedSe
     summary.push_str("  <th>Date</th>\n");
     summary.push_str("  <th>Email</th>\n");
     summary.push_str("  <th>File</th>\n");
+    if show_owners {
+        summary.push_str("  <th>Owners</th>\n");
+    }
     summary.push_str("</tr>\n");
 
     for finding in findings {
@@ -59,6 +93,12 @@ pub fn generate_findings_summary(repository: &Repository, findings: &[DetectedSe
             file_url,
             escape_html(&finding.file_path)
         ));
+        if show_owners {
+            summary.push_str(&format!(
+                "  <td>{}</td>\n",
+                escape_html(&finding.owners.join(" "))
+            ));
+        }
         summary.push_str("</tr>\n");
     }
 
@@ -68,7 +108,7 @@ pub fn generate_findings_summary(repository: &Repository, findings: &[DetectedSe
 }
 
 /// Escape HTML special characters
-fn escape_html(text: &str) -> String {
+pub(crate) fn escape_html(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -111,6 +151,13 @@ mod tests {
         assert!(summary.contains("✅"));
     }
 
+    #[test]
+    fn test_generate_base_token_note() {
+        let note = generate_base_token_note("pull_request_target");
+        assert!(note.starts_with("> [!WARNING]\n> This scan ran on `pull_request_target`"));
+        assert!(note.contains("withheld all tokens"));
+    }
+
     #[test]
     fn test_generate_error_summary() {
         let summary = generate_error_summary(1);
@@ -135,7 +182,7 @@ mod tests {
             html_url: "https://github.com/owner/repo".to_string(),
         };
 
-        let findings = vec![DetectedSecret {
+        let mut findings = vec![DetectedSecret {
             rule_id: "aws-access-token".to_string(),
             file_path: "src/config.rs".to_string(),
             line_number: 42,
@@ -144,6 +191,7 @@ mod tests {
             email: "john@example.com".to_string(),
             date: "2025-10-16".to_string(),
             fingerprint: "abc123def456:src/config.rs:aws-access-token:42".to_string(),
+            owners: Vec::new(),
         }];
 
         let summary = generate_findings_summary(&repository, &findings);
@@ -155,5 +203,11 @@ mod tests {
         assert!(summary.contains("John Doe"));
         assert!(summary.contains("src/config.rs"));
         assert!(summary.contains("https://github.com/owner/repo/commit/abc123def456"));
+        assert!(!summary.contains("Owners"));
+
+        findings[0].owners = vec!["@org/backend".to_string()];
+        let summary = generate_findings_summary(&repository, &findings);
+        assert!(summary.contains("<th>Owners</th>"));
+        assert!(summary.contains("<td>@org/backend</td>"));
     }
 }
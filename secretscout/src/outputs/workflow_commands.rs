@@ -0,0 +1,186 @@
+//! Workflow commands and step outputs
+//!
+//! `::error` workflow commands show each finding inline in the Actions UI
+//! without any API permissions. Step outputs written to `GITHUB_OUTPUT` let
+//! later steps act on the result without re-parsing the SARIF report.
+
+use crate::sarif::types::DetectedSecret;
+use std::io::Write;
+use std::path::Path;
+
+/// Number of `::error` workflow commands GitHub shows for a step
+///
+/// Further commands are dropped, so findings beyond this are summed up in a
+/// notice instead.
+pub const MAX_ANNOTATIONS: usize = 10;
+
+/// Format the `::error` workflow command for a finding
+pub fn format_annotation(finding: &DetectedSecret) -> String {
+    format!(
+        "::error file={},line={},title={}::{}",
+        escape_property(&finding.file_path),
+        finding.line_number,
+        escape_property(&format!("Secret detected: {}", finding.rule_id)),
+        escape_data(&format!(
+            "Gitleaks rule {} matched in commit {} by {}. Fingerprint: {}",
+            finding.rule_id,
+            finding.short_sha(),
+            finding.author,
+            finding.fingerprint
+        ))
+    )
+}
+
+/// Format the `::notice` workflow command for findings left unannotated
+pub fn format_omitted_annotations(omitted: usize) -> String {
+    format!(
+        "::notice title=SecretScout::{} more finding(s) not annotated, as GitHub shows only {} per step",
+        omitted, MAX_ANNOTATIONS
+    )
+}
+
+/// Build the step outputs for a scan
+///
+/// `findings` is a JSON array of the findings, in the same shape as
+/// [`DetectedSecret`]; `findings_count` also counts findings left out of it,
+/// and `findings-truncated` tells whether any were.
+pub fn build_step_outputs(
+    sarif_path: &Path,
+    findings: &[DetectedSecret],
+    findings_count: usize,
+    exit_code: i32,
+) -> Vec<(&'static str, String)> {
+    vec![
+        ("findings-count", findings_count.to_string()),
+        ("sarif-path", sarif_path.display().to_string()),
+        ("exit-code", exit_code.to_string()),
+        (
+            "findings-truncated",
+            (findings.len() < findings_count).to_string(),
+        ),
+        (
+            "findings",
+            serde_json::to_string(findings).unwrap_or_else(|_| "[]".to_string()),
+        ),
+    ]
+}
+
+/// Format a step output in `GITHUB_OUTPUT` syntax
+///
+/// Values spanning several lines use a heredoc-style delimiter.
+pub fn format_output(name: &str, value: &str) -> String {
+    if !value.contains('\n') {
+        return format!("{}={}\n", name, value);
+    }
+
+    let delimiter = loop {
+        let candidate = format!("ghadelimiter_{:016x}", fastrand::u64(..));
+        if !value.contains(&candidate) {
+            break candidate;
+        }
+    };
+    format!("{}<<{}\n{}\n{}\n", name, delimiter, value, delimiter)
+}
+
+/// Append step outputs to the GITHUB_OUTPUT file
+#[cfg(feature = "native")]
+pub fn write_step_outputs(outputs: &[(&str, String)]) -> std::io::Result<()> {
+    use std::env;
+    use std::fs::OpenOptions;
+
+    if let Ok(output_path) = env::var("GITHUB_OUTPUT") {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(output_path)?;
+
+        for (name, value) in outputs {
+            file.write_all(format_output(name, value).as_bytes())?;
+        }
+
+        log::debug!("Wrote {} step outputs to GITHUB_OUTPUT", outputs.len());
+        Ok(())
+    } else {
+        log::warn!("GITHUB_OUTPUT not set, cannot write step outputs");
+        Ok(())
+    }
+}
+
+/// Escape the message of a workflow command
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a property of a workflow command
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding() -> DetectedSecret {
+        DetectedSecret {
+            rule_id: "aws-access-token".to_string(),
+            file_path: "src/a,b.rs".to_string(),
+            line_number: 42,
+            commit_sha: "abc123def456".to_string(),
+            author: "Jane".to_string(),
+            email: "jane@example.com".to_string(),
+            date: "2025-10-16".to_string(),
+            fingerprint: "abc123def456:src/a,b.rs:aws-access-token:42".to_string(),
+            owners: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_format_annotation() {
+        assert_eq!(
+            format_annotation(&finding()),
+            "::error file=src/a%2Cb.rs,line=42,title=Secret detected%3A aws-access-token\
+             ::Gitleaks rule aws-access-token matched in commit abc123d by Jane. \
+             Fingerprint: abc123def456:src/a,b.rs:aws-access-token:42"
+        );
+    }
+
+    #[test]
+    fn test_format_omitted_annotations() {
+        assert_eq!(
+            format_omitted_annotations(5),
+            "::notice title=SecretScout::5 more finding(s) not annotated, as GitHub shows only 10 per step"
+        );
+    }
+
+    #[test]
+    fn test_escape_data() {
+        assert_eq!(escape_data("100%\r\nnext"), "100%25%0D%0Anext");
+    }
+
+    #[test]
+    fn test_build_step_outputs() {
+        let outputs = build_step_outputs(Path::new("/ws/results.sarif"), &[finding()], 1, 1);
+        assert_eq!(outputs[0], ("findings-count", "1".to_string()));
+        assert_eq!(outputs[1], ("sarif-path", "/ws/results.sarif".to_string()));
+        assert_eq!(outputs[2], ("exit-code", "1".to_string()));
+        assert_eq!(outputs[3], ("findings-truncated", "false".to_string()));
+
+        let findings: Vec<DetectedSecret> = serde_json::from_str(&outputs[4].1).unwrap();
+        assert_eq!(findings[0].rule_id, "aws-access-token");
+
+        let outputs = build_step_outputs(Path::new("/ws/results.sarif"), &[finding()], 1500, 1);
+        assert_eq!(outputs[3], ("findings-truncated", "true".to_string()));
+    }
+
+    #[test]
+    fn test_format_output() {
+        assert_eq!(format_output("exit-code", "0"), "exit-code=0\n");
+
+        let output = format_output("notes", "a\nb");
+        let delimiter = output.lines().next().unwrap().trim_start_matches("notes<<");
+        assert_eq!(output, format!("notes<<{0}\na\nb\n{0}\n", delimiter));
+    }
+}
@@ -0,0 +1,170 @@
+//! Report format conversion module
+//!
+//! This module normalizes reports in the formats gitleaks and SecretScout
+//! produce into `DetectedSecret` values, and renders findings into every
+//! report format SecretScout supports.
+
+pub mod read;
+pub mod write;
+
+use crate::error::{ReportError, Result};
+use std::str::FromStr;
+
+pub use read::read_findings;
+pub use write::{write_findings, ReportTotals, ReportWriter, WriteOptions};
+
+/// Formats a report can be read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    /// SARIF 2.1.0 as produced by `gitleaks --report-format=sarif`
+    Sarif,
+    /// JSON as produced by `gitleaks --report-format=json`
+    GitleaksJson,
+    /// CSV as produced by `gitleaks --report-format=csv` or `secretscout convert`
+    Csv,
+}
+
+/// Formats a report can be written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Sarif,
+    Json,
+    Csv,
+    Junit,
+    Markdown,
+    Html,
+    Gitlab,
+}
+
+impl FromStr for InputFormat {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sarif" => Ok(InputFormat::Sarif),
+            "gitleaks-json" => Ok(InputFormat::GitleaksJson),
+            "csv" => Ok(InputFormat::Csv),
+            _ => Err(ReportError::UnsupportedFormat {
+                direction: "input".to_string(),
+                format: s.to_string(),
+                expected: "sarif, gitleaks-json, csv".to_string(),
+            }
+            .into()),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sarif" => Ok(OutputFormat::Sarif),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "junit" => Ok(OutputFormat::Junit),
+            "markdown" => Ok(OutputFormat::Markdown),
+            "html" => Ok(OutputFormat::Html),
+            "gitlab" => Ok(OutputFormat::Gitlab),
+            _ => Err(ReportError::UnsupportedFormat {
+                direction: "output".to_string(),
+                format: s.to_string(),
+                expected: "sarif, json, csv, junit, markdown, html, gitlab".to_string(),
+            }
+            .into()),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// File extension of a report in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Sarif => "sarif",
+            OutputFormat::Json | OutputFormat::Gitlab => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Junit => "xml",
+            OutputFormat::Markdown => "md",
+            OutputFormat::Html => "html",
+        }
+    }
+}
+
+/// Split a CSV line into fields (RFC 4180 quoting)
+///
+/// Returns `None` while a quoted field is still open, so callers can join
+/// the next physical line and try again.
+pub(crate) fn split_csv_record(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut in_quotes = false;
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return None;
+    }
+
+    fields.push(field);
+    Some(fields)
+}
+
+/// Quote a CSV field when it contains separators, quotes or newlines
+pub(crate) fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_extension() {
+        assert_eq!(OutputFormat::Junit.extension(), "xml");
+        assert_eq!(OutputFormat::Markdown.extension(), "md");
+        assert_eq!(OutputFormat::Gitlab.extension(), "json");
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!(
+            InputFormat::from_str("gitleaks-json").unwrap(),
+            InputFormat::GitleaksJson
+        );
+        assert_eq!(
+            OutputFormat::from_str("gitlab").unwrap(),
+            OutputFormat::Gitlab
+        );
+        assert!(InputFormat::from_str("junit").is_err());
+        assert!(OutputFormat::from_str("xml").is_err());
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let fields = ["plain", "with,comma", "with \"quotes\"", "multi\nline"];
+        let line = fields
+            .iter()
+            .map(|f| escape_csv_field(f))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        assert_eq!(split_csv_record(&line).unwrap(), fields);
+        assert!(split_csv_record("\"open,field").is_none());
+    }
+}
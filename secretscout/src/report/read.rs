@@ -0,0 +1,260 @@
+//! Report readers
+//!
+//! Each reader normalizes its input into `DetectedSecret` values, filling
+//! in missing commit metadata with "unknown" like the SARIF extraction does.
+
+use super::{split_csv_record, InputFormat};
+use crate::error::{ReportError, Result};
+use crate::sarif::{self, types::DetectedSecret};
+use serde::Deserialize;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// A single finding from a gitleaks JSON report
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GitleaksFinding {
+    #[serde(rename = "RuleID")]
+    rule_id: String,
+    file: String,
+    start_line: u32,
+    #[serde(default)]
+    commit: String,
+    #[serde(default)]
+    author: String,
+    #[serde(default)]
+    email: String,
+    #[serde(default)]
+    date: String,
+    #[serde(default)]
+    fingerprint: String,
+}
+
+/// Read all findings from a report file
+pub fn read_findings(format: InputFormat, path: &Path) -> Result<Vec<DetectedSecret>> {
+    match format {
+        InputFormat::Sarif => sarif::stream_findings_from_file(path)?.collect(),
+        InputFormat::GitleaksJson => read_gitleaks_json(path),
+        InputFormat::Csv => read_csv(path),
+    }
+}
+
+/// Read a gitleaks JSON report (a top-level array of findings)
+fn read_gitleaks_json(path: &Path) -> Result<Vec<DetectedSecret>> {
+    let file = File::open(path)
+        .map_err(|e| ReportError::ReadFailed(format!("{}: {}", path.display(), e)))?;
+
+    let findings: Vec<GitleaksFinding> = serde_json::from_reader(BufReader::new(file))
+        .map_err(|e| ReportError::ReadFailed(format!("{}: {}", path.display(), e)))?;
+
+    Ok(findings
+        .into_iter()
+        .map(|f| {
+            build_secret(
+                f.rule_id,
+                f.file,
+                f.start_line,
+                f.commit,
+                f.author,
+                f.email,
+                f.date,
+                f.fingerprint,
+            )
+        })
+        .collect())
+}
+
+/// Read a CSV report, locating columns by their gitleaks header names
+fn read_csv(path: &Path) -> Result<Vec<DetectedSecret>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| ReportError::ReadFailed(format!("{}: {}", path.display(), e)))?;
+
+    parse_csv(&contents)
+}
+
+fn parse_csv(contents: &str) -> Result<Vec<DetectedSecret>> {
+    let mut records = Vec::new();
+    let mut pending = String::new();
+
+    for line in contents.lines() {
+        if !pending.is_empty() {
+            pending.push('\n');
+        }
+        pending.push_str(line);
+
+        if let Some(fields) = split_csv_record(&pending) {
+            records.push(fields);
+            pending.clear();
+        }
+    }
+
+    if !pending.is_empty() {
+        return Err(ReportError::ReadFailed("unterminated quoted CSV field".to_string()).into());
+    }
+
+    let mut records = records
+        .into_iter()
+        .filter(|r| r.iter().any(|f| !f.is_empty()));
+
+    let header = records
+        .next()
+        .ok_or_else(|| ReportError::ReadFailed("CSV report has no header row".to_string()))?;
+
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let required = |name: &str| {
+        column(name).ok_or_else(|| {
+            crate::error::Error::from(ReportError::ReadFailed(format!(
+                "CSV report has no '{}' column",
+                name
+            )))
+        })
+    };
+
+    let rule_col = required("RuleID")?;
+    let file_col = required("File")?;
+    let line_col = required("StartLine")?;
+    let commit_col = column("Commit");
+    let author_col = column("Author");
+    let email_col = column("Email");
+    let date_col = column("Date");
+    let fingerprint_col = column("Fingerprint");
+
+    records
+        .enumerate()
+        .map(|(index, record)| {
+            let get =
+                |col: Option<usize>| col.and_then(|c| record.get(c)).cloned().unwrap_or_default();
+
+            let line_number = get(Some(line_col)).trim().parse::<u32>().map_err(|_| {
+                ReportError::ReadFailed(format!(
+                    "row {}: invalid StartLine '{}'",
+                    index + 2,
+                    get(Some(line_col))
+                ))
+            })?;
+
+            Ok(build_secret(
+                get(Some(rule_col)),
+                get(Some(file_col)),
+                line_number,
+                get(commit_col),
+                get(author_col),
+                get(email_col),
+                get(date_col),
+                get(fingerprint_col),
+            ))
+        })
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_secret(
+    rule_id: String,
+    file_path: String,
+    line_number: u32,
+    commit_sha: String,
+    author: String,
+    email: String,
+    date: String,
+    fingerprint: String,
+) -> DetectedSecret {
+    let or_unknown = |value: String| {
+        if value.is_empty() {
+            "unknown".to_string()
+        } else {
+            value
+        }
+    };
+
+    let commit_sha = or_unknown(commit_sha);
+    let fingerprint = if fingerprint.is_empty() {
+        DetectedSecret::generate_fingerprint(&commit_sha, &file_path, &rule_id, line_number)
+    } else {
+        fingerprint
+    };
+
+    DetectedSecret {
+        rule_id,
+        file_path,
+        line_number,
+        commit_sha,
+        author: or_unknown(author),
+        email: or_unknown(email),
+        date: or_unknown(date),
+        fingerprint,
+        owners: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_read_gitleaks_json() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(
+            br#"[{
+                "Description": "AWS Access Key",
+                "StartLine": 42,
+                "EndLine": 42,
+                "Match": "AKIA...",
+                "Secret": "REDACTED",
+                "File": "src/config.rs",
+                "Commit": "abc123",
+                "Author": "Jane",
+                "Email": "jane@example.com",
+                "Date": "2025-10-16T12:00:00Z",
+                "Tags": [],
+                "RuleID": "aws-access-token",
+                "Fingerprint": "abc123:src/config.rs:aws-access-token:42"
+            }, {
+                "StartLine": 3,
+                "File": "notes.txt",
+                "Commit": "",
+                "RuleID": "generic-api-key"
+            }]"#,
+        )
+        .unwrap();
+
+        let findings = read_findings(InputFormat::GitleaksJson, file.path()).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].rule_id, "aws-access-token");
+        assert_eq!(findings[0].author, "Jane");
+        assert_eq!(
+            findings[0].fingerprint,
+            "abc123:src/config.rs:aws-access-token:42"
+        );
+        assert_eq!(findings[1].commit_sha, "unknown");
+        assert_eq!(
+            findings[1].fingerprint,
+            "unknown:notes.txt:generic-api-key:3"
+        );
+    }
+
+    #[test]
+    fn test_parse_gitleaks_csv() {
+        let csv = "RuleID,Commit,File,SymlinkFile,Secret,Match,StartLine,EndLine,StartColumn,EndColumn,Author,Message,Date,Email,Fingerprint,Tags\n\
+                   aws-access-token,abc123,src/config.rs,,REDACTED,\"key = \"\"x\"\"\",42,42,1,20,Jane,\"fix,\nmore\",2025-10-16,jane@example.com,abc123:src/config.rs:aws-access-token:42,\n";
+
+        let findings = parse_csv(csv).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].file_path, "src/config.rs");
+        assert_eq!(findings[0].line_number, 42);
+        assert_eq!(findings[0].email, "jane@example.com");
+    }
+
+    #[test]
+    fn test_parse_csv_errors() {
+        assert!(parse_csv("").is_err());
+        assert!(parse_csv("Commit,File\nabc,a.rs\n").is_err());
+        assert!(parse_csv("RuleID,File,StartLine\nrule,a.rs,x\n").is_err());
+    }
+}
@@ -0,0 +1,829 @@
+//! Report writers
+//!
+//! Renders findings into every supported output format. Links in the
+//! markdown and HTML reports are only generated when a repository URL is
+//! known, since archived reports may outlive the repository they describe.
+//!
+//! [`ReportWriter`] writes findings one at a time, so reports on large scans
+//! are never held in memory. Only reports grouped by code owner need every
+//! finding up front.
+
+use super::{escape_csv_field, OutputFormat};
+use crate::error::{ReportError, Result};
+use crate::outputs::summary::escape_html;
+use crate::sarif::types::{
+    ArtifactLocation, DetectedSecret, Driver, Location, Message, PartialFingerprints,
+    PhysicalLocation, Region, Run, SarifReport, Tool,
+};
+use std::io::Write;
+
+/// Options shared by all writers
+#[derive(Debug, Clone, Default)]
+pub struct WriteOptions {
+    /// Repository web URL (e.g. `https://github.com/owner/repo`) for links
+    pub repository_url: Option<String>,
+    /// Group markdown and html reports by code owner
+    pub group_by_owner: bool,
+}
+
+/// What the headers of a report say about the findings it lists
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReportTotals {
+    /// Number of findings
+    pub findings: usize,
+    /// Whether any finding has been attributed to code owners
+    pub has_owners: bool,
+}
+
+impl ReportTotals {
+    /// Count a finding
+    pub fn add(&mut self, finding: &DetectedSecret) {
+        self.findings += 1;
+        self.has_owners |= !finding.owners.is_empty();
+    }
+}
+
+/// Render findings in the given format
+pub fn write_findings(
+    format: OutputFormat,
+    findings: &[DetectedSecret],
+    options: &WriteOptions,
+    writer: &mut impl Write,
+) -> Result<()> {
+    if options.group_by_owner && matches!(format, OutputFormat::Markdown | OutputFormat::Html) {
+        let rendered = match format {
+            OutputFormat::Markdown => render_grouped_markdown(findings, options),
+            _ => render_grouped_html(findings, options),
+        };
+        return writer
+            .write_all(rendered.as_bytes())
+            .map_err(|e| ReportError::WriteFailed(e.to_string()).into());
+    }
+
+    let mut totals = ReportTotals::default();
+    findings.iter().for_each(|finding| totals.add(finding));
+
+    let mut report = ReportWriter::new(format, options, totals, writer)?;
+    for finding in findings {
+        report.write(finding)?;
+    }
+    report.finish()
+}
+
+/// Writes a report one finding at a time
+///
+/// The totals are written in the report's header, so they must describe the
+/// findings that are written. Findings are never grouped by code owner.
+pub struct ReportWriter<'a, W: Write> {
+    format: OutputFormat,
+    options: &'a WriteOptions,
+    totals: ReportTotals,
+    writer: W,
+    written: usize,
+    /// Indent of the elements of a JSON report's array, and what follows it
+    json_tail: Option<(String, String)>,
+}
+
+impl<'a, W: Write> ReportWriter<'a, W> {
+    /// Start a report by writing its header
+    pub fn new(
+        format: OutputFormat,
+        options: &'a WriteOptions,
+        totals: ReportTotals,
+        writer: W,
+    ) -> Result<Self> {
+        let mut report = ReportWriter {
+            format,
+            options,
+            totals,
+            writer,
+            written: 0,
+            json_tail: None,
+        };
+
+        match format {
+            OutputFormat::Sarif => report.start_json(&sarif_envelope()?, "\"results\": []")?,
+            OutputFormat::Json => report.start_json("[]\n", "[]")?,
+            OutputFormat::Gitlab => {
+                report.start_json(&gitlab_envelope()?, "\"vulnerabilities\": []")?
+            }
+            OutputFormat::Csv => report.put(CSV_HEADER)?,
+            OutputFormat::Junit => report.put(&junit_header(totals.findings))?,
+            OutputFormat::Markdown => report.put(&markdown_header(totals))?,
+            OutputFormat::Html => report.put(&html_header(totals))?,
+        }
+
+        Ok(report)
+    }
+
+    /// Write a finding
+    pub fn write(&mut self, finding: &DetectedSecret) -> Result<()> {
+        let show_owners = self.totals.has_owners;
+        match self.format {
+            OutputFormat::Sarif => self.write_json(&sarif_result(finding))?,
+            OutputFormat::Json => self.write_json(finding)?,
+            OutputFormat::Gitlab => self.write_json(&gitlab_vulnerability(finding))?,
+            OutputFormat::Csv => self.put(&csv_row(finding))?,
+            OutputFormat::Junit => self.put(&junit_testcase(finding))?,
+            OutputFormat::Markdown => {
+                let row = markdown_row(finding, self.options, show_owners);
+                self.put(&row)?
+            }
+            OutputFormat::Html => {
+                let row = html_row(finding, self.options, show_owners);
+                self.put(&row)?
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Finish the report and flush it
+    pub fn finish(mut self) -> Result<()> {
+        if let Some((indent, suffix)) = self.json_tail.take() {
+            if self.written > 0 {
+                // The closing bracket is one level out from the elements
+                self.put(&format!("\n{}", &indent[2..]))?;
+            }
+            self.put(&suffix)?;
+        }
+
+        match self.format {
+            OutputFormat::Junit => {
+                if self.totals.findings == 0 {
+                    self.put(JUNIT_NO_FINDINGS)?;
+                }
+                self.put(JUNIT_FOOTER)?;
+            }
+            OutputFormat::Html => {
+                if self.totals.findings > 0 {
+                    self.put("</table>\n")?;
+                }
+                self.put(HTML_FOOTER)?;
+            }
+            _ => {}
+        }
+
+        self.writer
+            .flush()
+            .map_err(|e| ReportError::WriteFailed(e.to_string()).into())
+    }
+
+    /// Write a JSON report up to the opening bracket of its findings' array
+    ///
+    /// The envelope is the complete report without findings, whose array is
+    /// `array`. The findings are written as `serde_json` would pretty print
+    /// them.
+    fn start_json(&mut self, envelope: &str, array: &str) -> Result<()> {
+        let start = envelope
+            .find(array)
+            .ok_or_else(|| ReportError::WriteFailed(format!("{} missing", array)))?;
+        let bracket = start + array.len() - 1;
+
+        let line_start = envelope[..start].rfind('\n').map_or(0, |i| i + 1);
+        let indent: String = envelope[line_start..]
+            .chars()
+            .take_while(|&c| c == ' ')
+            .collect();
+
+        self.put(&envelope[..bracket])?;
+        self.json_tail = Some((indent + "  ", envelope[bracket..].to_string()));
+        Ok(())
+    }
+
+    /// Write an element of a JSON report's findings array
+    fn write_json(&mut self, value: &impl serde::Serialize) -> Result<()> {
+        let indent = match &self.json_tail {
+            Some((indent, _)) => indent.clone(),
+            None => String::new(),
+        };
+        let element = serde_json::to_string_pretty(value)
+            .map_err(|e| ReportError::WriteFailed(e.to_string()))?
+            .replace('\n', &format!("\n{}", indent));
+
+        let separator = if self.written == 0 { "" } else { "," };
+        self.put(&format!("{}\n{}{}", separator, indent, element))
+    }
+
+    fn put(&mut self, text: &str) -> Result<()> {
+        self.writer
+            .write_all(text.as_bytes())
+            .map_err(|e| ReportError::WriteFailed(e.to_string()).into())
+    }
+}
+
+/// A SARIF report without results
+fn sarif_envelope() -> Result<String> {
+    let report = SarifReport {
+        schema: Some("https://json.schemastore.org/sarif-2.1.0.json".to_string()),
+        version: "2.1.0".to_string(),
+        runs: vec![Run {
+            tool: Tool {
+                driver: Driver {
+                    name: "gitleaks".to_string(),
+                    version: None,
+                    information_uri: Some("https://github.com/gitleaks/gitleaks".to_string()),
+                },
+            },
+            results: Vec::new(),
+        }],
+    };
+
+    serde_json::to_string_pretty(&report)
+        .map(|s| s + "\n")
+        .map_err(|e| ReportError::WriteFailed(e.to_string()).into())
+}
+
+fn sarif_result(finding: &DetectedSecret) -> crate::sarif::types::Result {
+    crate::sarif::types::Result {
+        rule_id: finding.rule_id.clone(),
+        message: Message {
+            text: format!(
+                "{} has detected secret for file {} at commit {}.",
+                finding.rule_id, finding.file_path, finding.commit_sha
+            ),
+        },
+        locations: vec![Location {
+            physical_location: PhysicalLocation {
+                artifact_location: ArtifactLocation {
+                    uri: finding.file_path.clone(),
+                },
+                region: Region {
+                    start_line: finding.line_number,
+                    start_column: None,
+                    end_line: None,
+                    end_column: None,
+                    snippet: None,
+                },
+            },
+        }],
+        partial_fingerprints: Some(PartialFingerprints {
+            commit_sha: Some(finding.commit_sha.clone()),
+            author: Some(finding.author.clone()),
+            email: Some(finding.email.clone()),
+            date: Some(finding.date.clone()),
+        }),
+        level: None,
+    }
+}
+
+const CSV_HEADER: &str = "RuleID,Commit,File,StartLine,Author,Email,Date,Fingerprint\n";
+
+fn csv_row(finding: &DetectedSecret) -> String {
+    let fields = [
+        finding.rule_id.as_str(),
+        finding.commit_sha.as_str(),
+        finding.file_path.as_str(),
+        &finding.line_number.to_string(),
+        finding.author.as_str(),
+        finding.email.as_str(),
+        finding.date.as_str(),
+        finding.fingerprint.as_str(),
+    ]
+    .map(escape_csv_field);
+
+    fields.join(",") + "\n"
+}
+
+fn junit_header(findings: usize) -> String {
+    let tests = findings.max(1);
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuites name=\"secretscout\" tests=\"{}\" failures=\"{}\">\n",
+        tests, findings
+    ));
+    out.push_str(&format!(
+        "  <testsuite name=\"secret detection\" tests=\"{}\" failures=\"{}\">\n",
+        tests, findings
+    ));
+    out
+}
+
+const JUNIT_NO_FINDINGS: &str =
+    "    <testcase classname=\"secretscout\" name=\"no secrets detected\"/>\n";
+
+const JUNIT_FOOTER: &str = "  </testsuite>\n</testsuites>\n";
+
+fn junit_testcase(finding: &DetectedSecret) -> String {
+    let mut out = format!(
+        "    <testcase classname=\"{}\" name=\"{} at line {}\">\n",
+        escape_html(&finding.file_path),
+        escape_html(&finding.rule_id),
+        finding.line_number
+    );
+    out.push_str(&format!(
+        "      <failure type=\"{}\" message=\"Secret detected in {}:{}\">",
+        escape_html(&finding.rule_id),
+        escape_html(&finding.file_path),
+        finding.line_number
+    ));
+    out.push_str(&escape_html(&format!(
+        "Rule: {}\nCommit: {}\nAuthor: {} <{}>\nDate: {}\nFingerprint: {}",
+        finding.rule_id,
+        finding.commit_sha,
+        finding.author,
+        finding.email,
+        finding.date,
+        finding.fingerprint
+    )));
+    out.push_str("</failure>\n");
+    out.push_str("    </testcase>\n");
+    out
+}
+
+fn markdown_header(totals: ReportTotals) -> String {
+    if totals.findings == 0 {
+        return "## No leaks detected ✅\n".to_string();
+    }
+
+    format!(
+        "## 🛑 {} secret(s) detected\n\n{}",
+        totals.findings,
+        markdown_table_header(totals.has_owners)
+    )
+}
+
+fn render_grouped_markdown(findings: &[DetectedSecret], options: &WriteOptions) -> String {
+    if findings.is_empty() {
+        return "## No leaks detected ✅\n".to_string();
+    }
+
+    let mut out = format!("## 🛑 {} secret(s) detected\n\n", findings.len());
+    let show_owners = has_owners(findings);
+
+    for (owner, group) in group_by_owner(findings) {
+        out.push_str(&format!(
+            "### {} ({})\n\n",
+            escape_markdown(&owner),
+            group.len()
+        ));
+        out.push_str(&markdown_table_header(show_owners));
+        for finding in group {
+            out.push_str(&markdown_row(finding, options, show_owners));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+fn markdown_table_header(show_owners: bool) -> String {
+    let mut out = String::from("| Rule ID | File | Line | Commit | Author | Date | Fingerprint |");
+    if show_owners {
+        out.push_str(" Owners |\n|---|---|---|---|---|---|---|---|\n");
+    } else {
+        out.push_str("\n|---|---|---|---|---|---|---|\n");
+    }
+    out
+}
+
+fn markdown_row(finding: &DetectedSecret, options: &WriteOptions, show_owners: bool) -> String {
+    let (file, commit) = match &options.repository_url {
+        Some(url) => (
+            format!(
+                "[{}]({})",
+                escape_markdown(&finding.file_path),
+                escape_markdown_url(&finding.secret_url(url))
+            ),
+            format!(
+                "[{}]({})",
+                finding.short_sha(),
+                escape_markdown_url(&finding.commit_url(url))
+            ),
+        ),
+        None => (
+            escape_markdown(&finding.file_path),
+            format!("`{}`", finding.short_sha()),
+        ),
+    };
+
+    let mut out = format!(
+        "| {} | {} | {} | {} | {} | {} | {} |",
+        markdown_code(&finding.rule_id),
+        file,
+        finding.line_number,
+        commit,
+        escape_markdown(&finding.author),
+        escape_markdown(&finding.date),
+        markdown_code(&finding.fingerprint)
+    );
+    if show_owners {
+        out.push_str(&format!(
+            " {} |",
+            escape_markdown(&finding.owners.join(" "))
+        ));
+    }
+    out.push('\n');
+    out
+}
+
+const HTML_HEAD: &str = "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n\
+     <meta charset=\"utf-8\">\n<title>SecretScout Report</title>\n\
+     <style>body{font-family:sans-serif}table{border-collapse:collapse}\
+     th,td{border:1px solid #ccc;padding:4px 8px;text-align:left}</style>\n\
+     </head>\n<body>\n";
+
+const HTML_FOOTER: &str = "</body>\n</html>\n";
+
+fn html_header(totals: ReportTotals) -> String {
+    let mut out = String::from(HTML_HEAD);
+    if totals.findings == 0 {
+        out.push_str("<h2>No leaks detected ✅</h2>\n");
+    } else {
+        out.push_str(&format!(
+            "<h2>🛑 {} secret(s) detected</h2>\n",
+            totals.findings
+        ));
+        out.push_str(&html_table_header(totals.has_owners));
+    }
+    out
+}
+
+fn render_grouped_html(findings: &[DetectedSecret], options: &WriteOptions) -> String {
+    if findings.is_empty() {
+        return html_header(ReportTotals::default()) + HTML_FOOTER;
+    }
+
+    let mut out = String::from(HTML_HEAD);
+    out.push_str(&format!(
+        "<h2>🛑 {} secret(s) detected</h2>\n",
+        findings.len()
+    ));
+    let show_owners = has_owners(findings);
+
+    for (owner, group) in group_by_owner(findings) {
+        out.push_str(&format!(
+            "<h3>{} ({})</h3>\n",
+            escape_html(&owner),
+            group.len()
+        ));
+        out.push_str(&html_table_header(show_owners));
+        for finding in group {
+            out.push_str(&html_row(finding, options, show_owners));
+        }
+        out.push_str("</table>\n");
+    }
+
+    out.push_str(HTML_FOOTER);
+    out
+}
+
+fn html_table_header(show_owners: bool) -> String {
+    let mut out = String::from("<table>\n<tr><th>Rule ID</th><th>File</th><th>Line</th><th>Commit</th><th>Author</th><th>Email</th><th>Date</th><th>Fingerprint</th>");
+    if show_owners {
+        out.push_str("<th>Owners</th>");
+    }
+    out.push_str("</tr>\n");
+    out
+}
+
+fn html_row(finding: &DetectedSecret, options: &WriteOptions, show_owners: bool) -> String {
+    let (file, commit) = match &options.repository_url {
+        Some(url) => (
+            format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(&finding.secret_url(url)),
+                escape_html(&finding.file_path)
+            ),
+            format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(&finding.commit_url(url)),
+                escape_html(finding.short_sha())
+            ),
+        ),
+        None => (
+            escape_html(&finding.file_path),
+            escape_html(finding.short_sha()),
+        ),
+    };
+
+    let mut out = format!(
+        "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><code>{}</code></td>",
+        escape_html(&finding.rule_id),
+        file,
+        finding.line_number,
+        commit,
+        escape_html(&finding.author),
+        escape_html(&finding.email),
+        escape_html(&finding.date),
+        escape_html(&finding.fingerprint)
+    );
+    if show_owners {
+        out.push_str(&format!(
+            "<td>{}</td>",
+            escape_html(&finding.owners.join(" "))
+        ));
+    }
+    out.push_str("</tr>\n");
+    out
+}
+
+/// Whether any finding has been attributed to code owners
+fn has_owners(findings: &[DetectedSecret]) -> bool {
+    findings.iter().any(|f| !f.owners.is_empty())
+}
+
+/// Group findings by owner, in order of first appearance
+///
+/// A finding with several owners is listed under each of them. Findings
+/// without owners come last, under "Unowned".
+fn group_by_owner(findings: &[DetectedSecret]) -> Vec<(String, Vec<&DetectedSecret>)> {
+    let mut groups: Vec<(String, Vec<&DetectedSecret>)> = Vec::new();
+    let mut unowned = Vec::new();
+
+    for finding in findings {
+        if finding.owners.is_empty() {
+            unowned.push(finding);
+        }
+        for owner in &finding.owners {
+            match groups.iter_mut().find(|(name, _)| name == owner) {
+                Some((_, group)) => group.push(finding),
+                None => groups.push((owner.clone(), vec![finding])),
+            }
+        }
+    }
+
+    if !unowned.is_empty() {
+        groups.push(("Unowned".to_string(), unowned));
+    }
+    groups
+}
+
+/// A GitLab secret detection report (schema 15.x) without vulnerabilities
+fn gitlab_envelope() -> Result<String> {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+
+    let report = serde_json::json!({
+        "version": "15.0.7",
+        "vulnerabilities": [],
+        "scan": {
+            "analyzer": {
+                "id": "secretscout",
+                "name": "SecretScout",
+                "version": crate::VERSION,
+                "vendor": { "name": "SecretScout" },
+            },
+            "scanner": {
+                "id": "gitleaks",
+                "name": "Gitleaks",
+                "version": "unknown",
+                "vendor": { "name": "Gitleaks" },
+            },
+            "type": "secret_detection",
+            "start_time": now,
+            "end_time": now,
+            "status": "success",
+        },
+    });
+
+    serde_json::to_string_pretty(&report)
+        .map(|s| s + "\n")
+        .map_err(|e| ReportError::WriteFailed(e.to_string()).into())
+}
+
+fn gitlab_vulnerability(finding: &DetectedSecret) -> serde_json::Value {
+    serde_json::json!({
+        "id": finding.fingerprint,
+        "category": "secret_detection",
+        "name": finding.rule_id,
+        "description": format!("{} detected in {}", finding.rule_id, finding.file_path),
+        "severity": "Critical",
+        "scanner": { "id": "gitleaks", "name": "Gitleaks" },
+        "location": {
+            "file": finding.file_path,
+            "start_line": finding.line_number,
+            "commit": {
+                "author": finding.author,
+                "date": finding.date,
+                "sha": finding.commit_sha,
+            },
+        },
+        "identifiers": [{
+            "type": "gitleaks_rule_id",
+            "name": format!("Gitleaks rule ID {}", finding.rule_id),
+            "value": finding.rule_id,
+        }],
+    })
+}
+
+/// Escape characters that would break a markdown table cell
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace('`', "\\`")
+        .replace('\n', " ")
+}
+
+/// Render text as a code span in a markdown table cell
+///
+/// Backslashes do not escape backticks inside a code span, so the span is
+/// fenced with more backticks than the text has in a row instead.
+fn markdown_code(text: &str) -> String {
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run + 1);
+    let text = text.replace('|', "\\|").replace('\n', " ");
+
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{0} {1} {0}", fence, text)
+    } else {
+        format!("{0}{1}{0}", fence, text)
+    }
+}
+
+/// Percent-encode characters that would end a markdown link or table cell
+fn escape_markdown_url(url: &str) -> String {
+    url.replace('|', "%7C")
+        .replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn findings() -> Vec<DetectedSecret> {
+        vec![DetectedSecret {
+            rule_id: "aws-access-token".to_string(),
+            file_path: "src/config.rs".to_string(),
+            line_number: 42,
+            commit_sha: "abc123def456".to_string(),
+            author: "John <Doe>".to_string(),
+            email: "john@example.com".to_string(),
+            date: "2025-10-16".to_string(),
+            fingerprint: "abc123def456:src/config.rs:aws-access-token:42".to_string(),
+            owners: Vec::new(),
+        }]
+    }
+
+    fn render(format: OutputFormat, options: &WriteOptions) -> String {
+        let mut out = Vec::new();
+        write_findings(format, &findings(), options, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_sarif_round_trip() {
+        let sarif = render(OutputFormat::Sarif, &WriteOptions::default());
+        let parsed = crate::sarif::stream_findings(sarif.as_bytes())
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].fingerprint, findings()[0].fingerprint);
+        assert!(
+            crate::sarif::validate::validate_value(&serde_json::from_str(&sarif).unwrap())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_streamed_json_matches_serde() {
+        let mut two = findings();
+        two.push(two[0].clone());
+        two[1].owners = vec!["@alice".to_string()];
+
+        for findings in [Vec::new(), two] {
+            let mut out = Vec::new();
+            write_findings(
+                OutputFormat::Json,
+                &findings,
+                &WriteOptions::default(),
+                &mut out,
+            )
+            .unwrap();
+            let expected = serde_json::to_string_pretty(&findings).unwrap() + "\n";
+            assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+            let mut out = Vec::new();
+            write_findings(
+                OutputFormat::Sarif,
+                &findings,
+                &WriteOptions::default(),
+                &mut out,
+            )
+            .unwrap();
+            let mut report: SarifReport = serde_json::from_str(&sarif_envelope().unwrap()).unwrap();
+            report.runs[0].results = findings.iter().map(sarif_result).collect();
+            let expected = serde_json::to_string_pretty(&report).unwrap() + "\n";
+            assert_eq!(String::from_utf8(out).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let json = render(OutputFormat::Json, &WriteOptions::default());
+        let parsed: Vec<DetectedSecret> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0].author, "John <Doe>");
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = render(OutputFormat::Csv, &WriteOptions::default());
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("RuleID,Commit,File,StartLine,Author,Email,Date,Fingerprint")
+        );
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("aws-access-token,abc123def456,"));
+    }
+
+    #[test]
+    fn test_junit() {
+        let xml = render(OutputFormat::Junit, &WriteOptions::default());
+        assert!(xml.contains("failures=\"1\""));
+        assert!(xml.contains("John &lt;Doe&gt;"));
+
+        let mut out = Vec::new();
+        write_findings(OutputFormat::Junit, &[], &WriteOptions::default(), &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("failures=\"0\""));
+    }
+
+    #[test]
+    fn test_markdown_and_html_links() {
+        let options = WriteOptions {
+            repository_url: Some("https://github.com/owner/repo".to_string()),
+            ..Default::default()
+        };
+
+        let markdown = render(OutputFormat::Markdown, &options);
+        assert!(markdown.contains("https://github.com/owner/repo/commit/abc123def456"));
+
+        let html = render(OutputFormat::Html, &options);
+        assert!(html.contains("https://github.com/owner/repo/blob/abc123def456/src/config.rs#L42"));
+        assert!(html.contains("John &lt;Doe&gt;"));
+
+        let markdown = render(OutputFormat::Markdown, &WriteOptions::default());
+        assert!(!markdown.contains("https://"));
+    }
+
+    #[test]
+    fn test_markdown_escaping() {
+        let mut findings = findings();
+        findings[0].rule_id = "rule|`x`".to_string();
+        findings[0].file_path = "a|b `c` (d).rs".to_string();
+        findings[0].fingerprint = format!("abc123def456:{}:rule:42", findings[0].file_path);
+
+        let options = WriteOptions {
+            repository_url: Some("https://github.com/owner/repo".to_string()),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        write_findings(OutputFormat::Markdown, &findings, &options, &mut out).unwrap();
+        let markdown = String::from_utf8(out).unwrap();
+        let row = markdown.lines().last().unwrap();
+
+        assert!(row.starts_with("| `` rule\\|`x` `` | [a\\|b \\`c\\` (d).rs]("));
+        assert!(row.contains("/blob/abc123def456/a%7Cb%20`c`%20%28d%29.rs#L42)"));
+        assert!(row.ends_with("| ``abc123def456:a\\|b `c` (d).rs:rule:42`` |"));
+        // Every unescaped pipe is a cell boundary
+        assert_eq!(row.replace("\\|", "").matches('|').count(), 8);
+    }
+
+    #[test]
+    fn test_owners_grouping() {
+        let mut findings = findings();
+        findings[0].owners = vec!["@org/backend".to_string(), "@alice".to_string()];
+        let mut unowned = findings[0].clone();
+        unowned.owners.clear();
+        unowned.file_path = "README.md".to_string();
+        findings.push(unowned);
+
+        let groups = group_by_owner(&findings);
+        let names: Vec<&str> = groups.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["@org/backend", "@alice", "Unowned"]);
+        assert_eq!(groups[2].1[0].file_path, "README.md");
+
+        let options = WriteOptions {
+            group_by_owner: true,
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        write_findings(OutputFormat::Markdown, &findings, &options, &mut out).unwrap();
+        let markdown = String::from_utf8(out).unwrap();
+        assert!(markdown.contains("### @org/backend (1)"));
+        assert!(markdown.contains("### Unowned (1)"));
+        assert!(markdown.contains("| @org/backend @alice |"));
+
+        let mut out = Vec::new();
+        write_findings(OutputFormat::Json, &findings, &options, &mut out).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json[0]["owners"][1], "@alice");
+        assert!(json[1].get("owners").is_none());
+    }
+
+    #[test]
+    fn test_gitlab() {
+        let report: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Gitlab, &WriteOptions::default())).unwrap();
+
+        assert_eq!(report["scan"]["type"], "secret_detection");
+        assert_eq!(report["vulnerabilities"][0]["location"]["start_line"], 42);
+        assert_eq!(
+            report["vulnerabilities"][0]["identifiers"][0]["value"],
+            "aws-access-token"
+        );
+    }
+}
@@ -3,11 +3,15 @@
 //! This module handles parsing SARIF 2.1.0 output from gitleaks and
 //! extracting detected secrets with metadata.
 
+pub mod stream;
 pub mod types;
+pub mod validate;
 
 use crate::error::{Result, SarifError};
 use std::path::Path;
+pub use stream::{stream_findings, stream_findings_from_file, FindingStream};
 use types::{DetectedSecret, SarifReport};
+use validate::ValidationError;
 
 /// Parse a SARIF report from a file
 pub fn parse_sarif_file(path: impl AsRef<Path>) -> Result<SarifReport> {
@@ -71,23 +75,33 @@ pub fn extract_findings(report: &SarifReport) -> Result<Vec<DetectedSecret>> {
 }
 
 /// Parse SARIF file and extract findings in one step
+///
+/// Loads the whole report into memory; prefer [`stream_findings_from_file`]
+/// for reports of unbounded size.
 pub fn parse_and_extract(path: impl AsRef<Path>) -> Result<Vec<DetectedSecret>> {
     let report = parse_sarif_file(path)?;
     extract_findings(&report)
 }
 
-/// Validate SARIF structure without full parsing
-pub fn validate_sarif(path: impl AsRef<Path>) -> Result<()> {
+/// Validate a SARIF file against the SARIF 2.1.0 schema and GitHub's upload constraints
+///
+/// Returns the list of problems found, each tagged with a JSON pointer.
+/// An empty list means the file is valid. Unreadable or malformed JSON is
+/// reported as an error rather than as a validation problem.
+pub fn validate_sarif(path: impl AsRef<Path>) -> Result<Vec<ValidationError>> {
     let path = path.as_ref();
 
     if !path.exists() {
         return Err(SarifError::FileNotFound(path.display().to_string()).into());
     }
 
-    // Try to parse - if successful, it's valid
-    parse_sarif_file(path)?;
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| SarifError::ParseError(format!("Failed to read file: {}", e)))?;
+
+    let document: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|e| SarifError::ParseError(format!("Failed to parse JSON: {}", e)))?;
 
-    Ok(())
+    Ok(validate::validate_value(&document))
 }
 
 #[cfg(test)]
@@ -196,6 +210,26 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_sarif() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(create_test_sarif().as_bytes()).unwrap();
+        temp_file.flush().unwrap();
+
+        let errors = validate_sarif(temp_file.path()).unwrap();
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file
+            .write_all(br#"{"version": "2.1.0", "runs": [{"tool": {}}]}"#)
+            .unwrap();
+        temp_file.flush().unwrap();
+
+        let errors = validate_sarif(temp_file.path()).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].pointer, "/runs/0/tool");
+    }
+
     #[test]
     fn test_file_not_found() {
         let result = parse_sarif_file("/nonexistent/path.sarif");
@@ -0,0 +1,553 @@
+//! Streaming SARIF parsing
+//!
+//! Full-history scans can produce SARIF reports far larger than the memory
+//! available on a CI runner. [`FindingStream`] walks the JSON byte stream and
+//! only materializes one `runs[].results[]` entry at a time, so memory use is
+//! bounded by the size of the largest single result rather than the report.
+//! Everything outside the results arrays is skipped without being buffered.
+//! The same walker lets code scanning uploads copy a report token by token.
+
+use super::types::{self, DetectedSecret};
+use crate::error::{Result, SarifError};
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind, Read};
+use std::path::Path;
+
+/// Iterator over the findings of a SARIF report read from `R`
+pub struct FindingStream<R: Read> {
+    walker: JsonWalker<R>,
+    runs_seen: usize,
+    results_seen: usize,
+    skipped: usize,
+    done: bool,
+}
+
+/// Stream findings from any reader
+pub fn stream_findings<R: Read>(reader: R) -> FindingStream<R> {
+    FindingStream {
+        walker: JsonWalker::new(reader),
+        runs_seen: 0,
+        results_seen: 0,
+        skipped: 0,
+        done: false,
+    }
+}
+
+/// Stream findings from a SARIF file on disk
+pub fn stream_findings_from_file(path: impl AsRef<Path>) -> Result<FindingStream<File>> {
+    let path = path.as_ref();
+
+    if !path.exists() {
+        return Err(SarifError::FileNotFound(path.display().to_string()).into());
+    }
+
+    let file = File::open(path)
+        .map_err(|e| SarifError::ParseError(format!("Failed to read file: {}", e)))?;
+
+    Ok(stream_findings(file))
+}
+
+impl<R: Read> Iterator for FindingStream<R> {
+    type Item = Result<DetectedSecret>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.next_finding() {
+            Ok(Some(finding)) => Some(Ok(finding)),
+            Ok(None) => {
+                self.done = true;
+                log::info!(
+                    "Streamed {} results from {} SARIF run(s)",
+                    self.results_seen,
+                    self.runs_seen
+                );
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<R: Read> FindingStream<R> {
+    /// Number of results skipped so far because they did not convert
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Advance to the next result that converts into a finding
+    fn next_finding(&mut self) -> Result<Option<DetectedSecret>> {
+        while let Some(token) = self.walker.next_token()? {
+            match token {
+                Token::Start(open) if self.walker.at_result_position() => {
+                    let raw = self.walker.capture_value(open)?;
+                    let index = self.results_seen;
+                    self.results_seen += 1;
+
+                    match convert_result(&raw, index)? {
+                        Some(finding) => return Ok(Some(finding)),
+                        None => self.skipped += 1,
+                    }
+                }
+                Token::Start(open) => {
+                    if open == b'{' && self.walker.at_run_position() {
+                        self.runs_seen += 1;
+                    }
+                    self.walker.enter(open)?;
+                }
+                Token::Value if self.walker.at_result_position() => {
+                    return Err(self.walker.error("expected result object"));
+                }
+                _ => {}
+            }
+        }
+
+        if self.runs_seen == 0 {
+            return Err(
+                SarifError::InvalidStructure("No runs found in SARIF report".to_string()).into(),
+            );
+        }
+        Ok(None)
+    }
+}
+
+/// A token of a JSON document read by [`JsonWalker`]
+pub(crate) enum Token {
+    /// An object key, still escaped
+    Key(Vec<u8>),
+    /// The `{` or `[` of a container that has not been entered yet
+    Start(u8),
+    /// The end of a container
+    End(Frame),
+    /// A string, number, `true`, `false` or `null` value
+    Value,
+    /// A `,` or `:`
+    Separator,
+}
+
+/// An open JSON container and the key it was found under
+pub(crate) struct Frame {
+    pub(crate) is_object: bool,
+    pub(crate) key: Option<Vec<u8>>,
+}
+
+/// Pull tokenizer over a JSON document that tracks the containers it is in
+///
+/// Only keys are kept; values are skipped, or copied whole by
+/// [`JsonWalker::capture_value`]. While recording, the bytes of each token
+/// are kept too, so a document can be copied token by token.
+pub(crate) struct JsonWalker<R: Read> {
+    reader: BufReader<R>,
+    peeked: Option<u8>,
+    offset: u64,
+    stack: Vec<Frame>,
+    pending_key: Option<Vec<u8>>,
+    expect_key: bool,
+    started: bool,
+    recorded: Option<Vec<u8>>,
+}
+
+impl<R: Read> JsonWalker<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        JsonWalker {
+            reader: BufReader::new(reader),
+            peeked: None,
+            offset: 0,
+            stack: Vec::new(),
+            pending_key: None,
+            expect_key: false,
+            started: false,
+            recorded: None,
+        }
+    }
+
+    /// Keep the bytes read from now on, for [`JsonWalker::take_recorded`]
+    pub(crate) fn record(&mut self) {
+        self.recorded = Some(Vec::new());
+    }
+
+    /// Bytes read since the last call, including whitespace
+    pub(crate) fn take_recorded(&mut self) -> Vec<u8> {
+        self.recorded
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Read the next token, or `None` once a complete document has been read
+    ///
+    /// A [`Token::Start`] must be followed by [`JsonWalker::enter`] or
+    /// [`JsonWalker::capture_value`].
+    pub(crate) fn next_token(&mut self) -> Result<Option<Token>> {
+        let byte = match self.next_significant_byte()? {
+            Some(byte) => byte,
+            None if self.started && self.stack.is_empty() => return Ok(None),
+            None => return Err(self.error("unexpected end of input")),
+        };
+
+        let token = match byte {
+            b',' => {
+                self.expect_key = self.in_object();
+                Token::Separator
+            }
+            b':' => Token::Separator,
+            b'"' => {
+                let text = self.read_string(self.expect_key)?;
+                match text {
+                    Some(key) => {
+                        self.expect_key = false;
+                        self.pending_key = Some(key.clone());
+                        Token::Key(key)
+                    }
+                    None => {
+                        self.pending_key = None;
+                        Token::Value
+                    }
+                }
+            }
+            b'{' | b'[' => Token::Start(byte),
+            b'}' | b']' => Token::End(self.close(byte == b'}')?),
+            _ => {
+                self.skip_scalar(byte)?;
+                self.pending_key = None;
+                Token::Value
+            }
+        };
+        Ok(Some(token))
+    }
+
+    /// Number of open containers
+    pub(crate) fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// True inside the top-level `runs` array
+    pub(crate) fn in_runs(&self) -> bool {
+        self.stack.len() >= 2
+            && !self.stack[1].is_object
+            && self.stack[1].key.as_deref() == Some(b"runs".as_slice())
+    }
+
+    /// True when the next value is an element of `runs`
+    pub(crate) fn at_run_position(&self) -> bool {
+        self.stack.len() == 2 && self.in_runs()
+    }
+
+    /// True when the next value is an element of `runs[].results`
+    pub(crate) fn at_result_position(&self) -> bool {
+        self.stack.len() == 4
+            && !self.stack[3].is_object
+            && self.stack[3].key.as_deref() == Some(b"results".as_slice())
+            && self.stack[2].is_object
+            && self.in_runs()
+    }
+
+    fn in_object(&self) -> bool {
+        self.stack.last().is_some_and(|frame| frame.is_object)
+    }
+
+    /// Enter the container a [`Token::Start`] opened
+    pub(crate) fn enter(&mut self, open: u8) -> Result<()> {
+        let is_object = open == b'{';
+        if self.stack.is_empty() {
+            if self.started {
+                return Err(self.error("trailing data after SARIF document"));
+            }
+            if !is_object {
+                return Err(self.error("SARIF document must be a JSON object"));
+            }
+            self.started = true;
+        }
+
+        self.stack.push(Frame {
+            is_object,
+            key: self.pending_key.take(),
+        });
+        self.expect_key = is_object;
+        Ok(())
+    }
+
+    fn close(&mut self, is_object: bool) -> Result<Frame> {
+        match self.stack.pop() {
+            Some(frame) if frame.is_object == is_object => {
+                self.expect_key = false;
+                self.pending_key = None;
+                Ok(frame)
+            }
+            _ => Err(self.error("mismatched closing bracket")),
+        }
+    }
+
+    /// Read the rest of a string whose opening quote was consumed
+    ///
+    /// The raw (still escaped) bytes are only kept when `keep` is set, which
+    /// is the case for object keys. Keys we match on are plain ASCII.
+    fn read_string(&mut self, keep: bool) -> Result<Option<Vec<u8>>> {
+        let mut text = keep.then(Vec::new);
+
+        loop {
+            let byte = self
+                .next_byte()?
+                .ok_or_else(|| self.error("unterminated string"))?;
+            match byte {
+                b'"' => return Ok(text),
+                b'\\' => {
+                    let escaped = self
+                        .next_byte()?
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    if let Some(text) = text.as_mut() {
+                        text.extend_from_slice(&[b'\\', escaped]);
+                    }
+                }
+                _ => {
+                    if let Some(text) = text.as_mut() {
+                        text.push(byte);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Skip a number, `true`, `false` or `null`
+    fn skip_scalar(&mut self, first: u8) -> Result<()> {
+        if !(first == b'-' || first.is_ascii_alphanumeric()) {
+            return Err(self.error(&format!("unexpected character '{}'", first as char)));
+        }
+
+        while let Some(byte) = self.next_byte()? {
+            if !(byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'+' | b'-')) {
+                self.peeked = Some(byte);
+                self.offset -= 1;
+                if let Some(recorded) = self.recorded.as_mut() {
+                    recorded.pop();
+                }
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copy one complete JSON container (starting with `open`) into a buffer
+    pub(crate) fn capture_value(&mut self, open: u8) -> Result<Vec<u8>> {
+        let mut raw = vec![open];
+        let mut depth = 1usize;
+        let mut in_string = false;
+
+        while depth > 0 {
+            let byte = self
+                .next_byte()?
+                .ok_or_else(|| self.error("unexpected end of input inside result"))?;
+            raw.push(byte);
+
+            if in_string {
+                match byte {
+                    b'\\' => {
+                        let escaped = self
+                            .next_byte()?
+                            .ok_or_else(|| self.error("unterminated string"))?;
+                        raw.push(escaped);
+                    }
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+            } else {
+                match byte {
+                    b'"' => in_string = true,
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => depth -= 1,
+                    _ => {}
+                }
+            }
+        }
+
+        self.pending_key = None;
+        Ok(raw)
+    }
+
+    fn next_significant_byte(&mut self) -> Result<Option<u8>> {
+        while let Some(byte) = self.next_byte()? {
+            if !byte.is_ascii_whitespace() {
+                return Ok(Some(byte));
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_byte(&mut self) -> Result<Option<u8>> {
+        let byte = match self.peeked.take() {
+            Some(byte) => byte,
+            None => loop {
+                match self.reader.fill_buf() {
+                    Ok([]) => return Ok(None),
+                    Ok(buffer) => {
+                        let byte = buffer[0];
+                        self.reader.consume(1);
+                        break byte;
+                    }
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(e) => {
+                        return Err(
+                            SarifError::ParseError(format!("Failed to read file: {}", e)).into(),
+                        )
+                    }
+                }
+            },
+        };
+
+        self.offset += 1;
+        if let Some(recorded) = self.recorded.as_mut() {
+            recorded.push(byte);
+        }
+        Ok(Some(byte))
+    }
+
+    pub(crate) fn error(&self, message: &str) -> crate::error::Error {
+        SarifError::ParseError(format!(
+            "Failed to parse JSON: {} at byte {}",
+            message, self.offset
+        ))
+        .into()
+    }
+}
+
+/// Deserialize one raw result and convert it into a finding
+///
+/// Results without locations or fingerprints are skipped with a warning,
+/// matching [`super::extract_findings`].
+fn convert_result(raw: &[u8], index: usize) -> Result<Option<DetectedSecret>> {
+    let result: types::Result = serde_json::from_slice(raw)
+        .map_err(|e| SarifError::ParseError(format!("Failed to parse result {}: {}", index, e)))?;
+
+    if result.locations.is_empty() {
+        log::warn!("Skipping result without locations: {}", result.rule_id);
+        return Ok(None);
+    }
+
+    let finding = Option::<DetectedSecret>::from(&result);
+    if finding.is_none() {
+        log::warn!(
+            "Failed to extract secret from result: {} (missing fingerprints)",
+            result.rule_id
+        );
+    }
+
+    Ok(finding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn result(rule_id: &str, uri: &str, line: u32, sha: &str) -> serde_json::Value {
+        json!({
+            "ruleId": rule_id,
+            "message": { "text": "secret with \"quotes\", {braces} and [brackets]" },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": uri },
+                    "region": { "startLine": line, "snippet": { "text": "key = \"]}\\\\\"" } }
+                }
+            }],
+            "partialFingerprints": { "commitSha": sha, "author": "Jane", "email": "j@example.com", "date": "2025-10-16" }
+        })
+    }
+
+    fn collect(input: &str) -> Result<Vec<DetectedSecret>> {
+        stream_findings(input.as_bytes()).collect()
+    }
+
+    #[test]
+    fn test_stream_matches_full_parse() {
+        let report = json!({
+            "version": "2.1.0",
+            "runs": [
+                {
+                    "artifacts": [{ "location": { "uri": "results" } }, [1, 2, [3]]],
+                    "results": [result("aws-access-token", "src/a.rs", 1, "aaa"), result("generic-api-key", "src/b.rs", 2, "bbb")],
+                    "tool": { "driver": { "name": "gitleaks", "results": [{ "ignored": true }] } }
+                },
+                {
+                    "tool": { "driver": { "name": "gitleaks" } },
+                    "results": [result("slack-token", "c d.rs", 3, "ccc")]
+                }
+            ]
+        })
+        .to_string();
+
+        let streamed = collect(&report).unwrap();
+        let parsed =
+            crate::sarif::extract_findings(&crate::sarif::parse_sarif_str(&report).unwrap())
+                .unwrap();
+
+        assert_eq!(streamed.len(), 3);
+        for (a, b) in streamed.iter().zip(parsed.iter()) {
+            assert_eq!(a.fingerprint, b.fingerprint);
+            assert_eq!(a.author, b.author);
+        }
+        assert_eq!(streamed[2].file_path, "c d.rs");
+    }
+
+    #[test]
+    fn test_stream_skips_results_without_fingerprints() {
+        let mut incomplete = result("rule", "src/a.rs", 1, "aaa");
+        incomplete
+            .as_object_mut()
+            .unwrap()
+            .remove("partialFingerprints");
+
+        let report = json!({
+            "version": "2.1.0",
+            "runs": [{ "tool": { "driver": { "name": "gitleaks" } }, "results": [incomplete, result("rule", "src/b.rs", 2, "bbb")] }]
+        })
+        .to_string();
+
+        let mut stream = stream_findings(report.as_bytes());
+        let findings = stream.by_ref().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].file_path, "src/b.rs");
+        assert_eq!(stream.skipped(), 1);
+    }
+
+    #[test]
+    fn test_stream_empty_results() {
+        let report = r#"{"version": "2.1.0", "runs": [{"tool": {"driver": {"name": "gitleaks"}}, "results": []}]}"#;
+        assert!(collect(report).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_stream_requires_runs() {
+        assert!(collect(r#"{"version": "2.1.0", "runs": []}"#).is_err());
+        assert!(collect(r#"{"version": "2.1.0"}"#).is_err());
+    }
+
+    #[test]
+    fn test_stream_malformed_input() {
+        assert!(collect("").is_err());
+        assert!(collect(r#"{"version": "2.1.0", "runs": [{"results": [{"ruleId": "x""#).is_err());
+        assert!(collect(r#"{"runs": [}"#).is_err());
+        assert!(collect(r#"{"runs": [{"results": [42]}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_stream_invalid_result_reports_index() {
+        let report =
+            r#"{"runs": [{"tool": {"driver": {"name": "gitleaks"}}, "results": [{"ruleId": 5}]}]}"#;
+        let err = collect(report).unwrap_err();
+        assert!(err.to_string().contains("result 0"));
+    }
+
+    #[test]
+    fn test_stream_from_missing_file() {
+        assert!(matches!(
+            stream_findings_from_file("/nonexistent/path.sarif"),
+            Err(crate::error::Error::Sarif(SarifError::FileNotFound(_)))
+        ));
+    }
+}
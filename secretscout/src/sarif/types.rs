@@ -135,6 +135,9 @@ pub struct DetectedSecret {
     pub email: String,
     pub date: String,
     pub fingerprint: String,
+    /// CODEOWNERS of the file, when known
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
 }
 
 impl DetectedSecret {
@@ -225,6 +228,7 @@ impl From<&Result> for Option<DetectedSecret> {
             email,
             date,
             fingerprint,
+            owners: Vec::new(),
         })
     }
 }
@@ -251,6 +255,7 @@ mod tests {
             email: "test@example.com".to_string(),
             date: "2025-10-16".to_string(),
             fingerprint: "test".to_string(),
+            owners: Vec::new(),
         };
 
         assert_eq!(secret.short_sha(), "abcdef1");
@@ -267,6 +272,7 @@ mod tests {
             email: "test@example.com".to_string(),
             date: "2025-10-16".to_string(),
             fingerprint: "test".to_string(),
+            owners: Vec::new(),
         };
 
         let repo_url = "https://github.com/owner/repo";
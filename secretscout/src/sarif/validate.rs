@@ -0,0 +1,483 @@
+//! SARIF report validation
+//!
+//! Checks a SARIF document against the embedded SARIF 2.1.0 schema and
+//! against the additional constraints GitHub code scanning enforces on
+//! upload. Every problem is reported with the JSON pointer of the offending
+//! value so it can be located in large reports.
+
+use jsonschema::error::ValidationErrorKind;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Embedded OASIS SARIF 2.1.0 schema (no network access needed to validate)
+const SARIF_SCHEMA: &str = include_str!("sarif-schema-2.1.0.json");
+
+/// Maximum number of runs GitHub accepts in a single SARIF file
+pub const MAX_RUNS: usize = 20;
+
+/// Maximum number of results GitHub accepts per run
+pub const MAX_RESULTS_PER_RUN: usize = 25_000;
+
+/// Maximum number of rules GitHub accepts per tool component
+pub const MAX_RULES_PER_RUN: usize = 25_000;
+
+/// Maximum number of locations GitHub accepts per result
+pub const MAX_LOCATIONS_PER_RESULT: usize = 1_000;
+
+/// Maximum rule id length GitHub accepts
+pub const MAX_RULE_ID_LENGTH: usize = 255;
+
+/// A single validation problem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationError {
+    /// JSON pointer (RFC 6901) to the offending value, empty for the root
+    pub pointer: String,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+impl ValidationError {
+    fn new(pointer: &str, message: impl Into<String>) -> Self {
+        ValidationError {
+            pointer: pointer.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "/"
+        } else {
+            &self.pointer
+        };
+        write!(f, "{}: {}", pointer, self.message)
+    }
+}
+
+/// Validate a SARIF document against the schema and GitHub's constraints
+///
+/// Returns an empty list when the document is valid.
+pub fn validate_value(document: &Value) -> Vec<ValidationError> {
+    let mut errors = validate_schema(document);
+    errors.extend(validate_github_constraints(document));
+    errors
+}
+
+/// Validate a SARIF document against the embedded SARIF 2.1.0 schema
+pub fn validate_schema(document: &Value) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    for error in schema_validator().iter_errors(document) {
+        let pointer = error.instance_path().as_str();
+        match error.kind() {
+            // Point at each unexpected property rather than at its parent
+            ValidationErrorKind::AdditionalProperties { unexpected } => {
+                errors.extend(unexpected.iter().map(|name| {
+                    ValidationError::new(
+                        &format!("{}/{}", pointer, escape_pointer_token(name)),
+                        format!("property '{}' is not allowed here", name),
+                    )
+                }));
+            }
+            _ => errors.push(ValidationError::new(pointer, describe(&error))),
+        }
+    }
+    errors
+}
+
+/// Describe a schema violation, listing why each alternative failed
+fn describe(error: &jsonschema::ValidationError) -> String {
+    match error.kind() {
+        ValidationErrorKind::Required { property } => match property.as_str() {
+            Some(name) => format!("missing required property '{}'", name),
+            None => error.to_string(),
+        },
+        ValidationErrorKind::AnyOf { context } | ValidationErrorKind::OneOfNotValid { context } => {
+            let branches: Vec<String> = context
+                .iter()
+                .flatten()
+                .map(|branch| describe(branch))
+                .collect();
+            format!("must satisfy one of: {}", branches.join(" | "))
+        }
+        _ => error.to_string(),
+    }
+}
+
+/// Validator for the embedded schema, compiled on first use
+///
+/// Formats (`uri`, `date-time`, ...) are checked too, which draft 7
+/// validators skip by default.
+fn schema_validator() -> &'static jsonschema::Validator {
+    static VALIDATOR: OnceLock<jsonschema::Validator> = OnceLock::new();
+    VALIDATOR.get_or_init(|| {
+        let schema: Value =
+            serde_json::from_str(SARIF_SCHEMA).expect("embedded SARIF schema is valid JSON");
+        jsonschema::draft7::options()
+            .should_validate_formats(true)
+            .build(&schema)
+            .expect("embedded SARIF schema compiles")
+    })
+}
+
+/// Validate the limits and conventions GitHub code scanning adds on top of the schema
+pub fn validate_github_constraints(document: &Value) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    let runs = match document.get("runs").and_then(Value::as_array) {
+        Some(runs) => runs,
+        None => return errors,
+    };
+
+    if runs.len() > MAX_RUNS {
+        errors.push(ValidationError::new(
+            "/runs",
+            format!(
+                "GitHub accepts at most {} runs per file, found {}",
+                MAX_RUNS,
+                runs.len()
+            ),
+        ));
+    }
+
+    for (run_index, run) in runs.iter().enumerate() {
+        let run_pointer = format!("/runs/{}", run_index);
+
+        let rules = run
+            .pointer("/tool/driver/rules")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        if rules.len() > MAX_RULES_PER_RUN {
+            errors.push(ValidationError::new(
+                &format!("{}/tool/driver/rules", run_pointer),
+                format!(
+                    "GitHub accepts at most {} rules per run, found {}",
+                    MAX_RULES_PER_RUN,
+                    rules.len()
+                ),
+            ));
+        }
+
+        for (rule_index, rule) in rules.iter().enumerate() {
+            if let Some(id) = rule.get("id").and_then(Value::as_str) {
+                check_rule_id(
+                    id,
+                    &format!("{}/tool/driver/rules/{}/id", run_pointer, rule_index),
+                    &mut errors,
+                );
+            }
+        }
+
+        let results = match run.get("results").and_then(Value::as_array) {
+            Some(results) => results,
+            None => continue,
+        };
+
+        if results.len() > MAX_RESULTS_PER_RUN {
+            errors.push(ValidationError::new(
+                &format!("{}/results", run_pointer),
+                format!(
+                    "GitHub accepts at most {} results per run, found {}",
+                    MAX_RESULTS_PER_RUN,
+                    results.len()
+                ),
+            ));
+        }
+
+        for (result_index, result) in results.iter().enumerate() {
+            let result_pointer = format!("{}/results/{}", run_pointer, result_index);
+            validate_github_result(result, &result_pointer, &mut errors);
+        }
+    }
+
+    errors
+}
+
+/// Validate a single result against GitHub's constraints
+fn validate_github_result(result: &Value, pointer: &str, errors: &mut Vec<ValidationError>) {
+    match result.get("ruleId").and_then(Value::as_str) {
+        Some(id) => check_rule_id(id, &format!("{}/ruleId", pointer), errors),
+        None => errors.push(ValidationError::new(
+            pointer,
+            "GitHub requires 'ruleId' on every result",
+        )),
+    }
+
+    if result.pointer("/message/text").is_none() {
+        errors.push(ValidationError::new(
+            &format!("{}/message", pointer),
+            "GitHub requires 'message.text' on every result",
+        ));
+    }
+
+    match result.get("partialFingerprints").and_then(Value::as_object) {
+        Some(fingerprints) if !fingerprints.is_empty() => {}
+        Some(_) => errors.push(ValidationError::new(
+            &format!("{}/partialFingerprints", pointer),
+            "must contain at least one fingerprint",
+        )),
+        None => errors.push(ValidationError::new(
+            pointer,
+            "GitHub requires 'partialFingerprints' to track results across uploads",
+        )),
+    }
+
+    let locations = match result.get("locations").and_then(Value::as_array) {
+        Some(locations) => locations,
+        None => return,
+    };
+
+    if locations.len() > MAX_LOCATIONS_PER_RESULT {
+        errors.push(ValidationError::new(
+            &format!("{}/locations", pointer),
+            format!(
+                "GitHub accepts at most {} locations per result, found {}",
+                MAX_LOCATIONS_PER_RESULT,
+                locations.len()
+            ),
+        ));
+    }
+
+    for (location_index, location) in locations.iter().enumerate() {
+        let uri_pointer = format!(
+            "{}/locations/{}/physicalLocation/artifactLocation/uri",
+            pointer, location_index
+        );
+        if let Some(uri) = location
+            .pointer("/physicalLocation/artifactLocation/uri")
+            .and_then(Value::as_str)
+        {
+            if let Some(problem) = check_artifact_uri(uri) {
+                errors.push(ValidationError::new(&uri_pointer, problem));
+            }
+        }
+    }
+}
+
+/// Escape a property name for use in a JSON pointer (RFC 6901)
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Check a rule id against GitHub's length limit
+fn check_rule_id(id: &str, pointer: &str, errors: &mut Vec<ValidationError>) {
+    if id.is_empty() {
+        errors.push(ValidationError::new(pointer, "rule id must not be empty"));
+    } else if id.len() > MAX_RULE_ID_LENGTH {
+        errors.push(ValidationError::new(
+            pointer,
+            format!(
+                "rule id is {} characters long, GitHub accepts at most {}",
+                id.len(),
+                MAX_RULE_ID_LENGTH
+            ),
+        ));
+    }
+}
+
+/// Check that an artifact URI can be mapped to a repository file by GitHub
+///
+/// GitHub expects either a path relative to the repository root or an
+/// absolute `file://` URI, written as a valid URI reference.
+fn check_artifact_uri(uri: &str) -> Option<String> {
+    if uri.is_empty() {
+        return Some("URI must not be empty".to_string());
+    }
+
+    if uri.contains('\\') {
+        return Some(format!(
+            "URI '{}' contains backslashes, use forward slashes",
+            uri
+        ));
+    }
+
+    if uri.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Some(format!(
+            "URI '{}' contains whitespace or control characters, they must be percent-encoded",
+            uri
+        ));
+    }
+
+    if let Some((scheme, _)) = uri.split_once(':') {
+        let is_scheme = scheme
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+
+        if is_scheme && scheme.len() == 1 {
+            return Some(format!(
+                "URI '{}' is a Windows path, use a relative path or a file:// URI",
+                uri
+            ));
+        }
+
+        if is_scheme && !scheme.eq_ignore_ascii_case("file") {
+            return Some(format!(
+                "URI scheme '{}' is not supported, use a relative path or a file:// URI",
+                scheme
+            ));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn valid_report() -> Value {
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "gitleaks",
+                        "rules": [{ "id": "aws-access-token" }]
+                    }
+                },
+                "results": [{
+                    "ruleId": "aws-access-token",
+                    "message": { "text": "AWS Access Key detected" },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": "src/config.rs" },
+                            "region": { "startLine": 42 }
+                        }
+                    }],
+                    "partialFingerprints": { "commitSha": "abc123" }
+                }]
+            }]
+        })
+    }
+
+    fn pointers(errors: &[ValidationError]) -> Vec<&str> {
+        errors.iter().map(|e| e.pointer.as_str()).collect()
+    }
+
+    #[test]
+    fn test_valid_report() {
+        assert_eq!(validate_value(&valid_report()), Vec::new());
+    }
+
+    #[test]
+    fn test_schema_errors_carry_pointers() {
+        let mut report = valid_report();
+        report["version"] = json!("2.0.0");
+        report["runs"][0]["results"][0]["level"] = json!("fatal");
+        report["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"]
+            ["startLine"] = json!(0);
+        report["runs"][0]["tool"]["driver"]
+            .as_object_mut()
+            .unwrap()
+            .remove("name");
+
+        let errors = validate_schema(&report);
+        let pointers = pointers(&errors);
+
+        assert!(pointers.contains(&"/version"));
+        assert!(pointers.contains(&"/runs/0/results/0/level"));
+        assert!(
+            pointers.contains(&"/runs/0/results/0/locations/0/physicalLocation/region/startLine")
+        );
+        assert!(pointers.contains(&"/runs/0/tool/driver"));
+    }
+
+    #[test]
+    fn test_schema_checks_formats_patterns_and_unique_items() {
+        let mut report = valid_report();
+        report["$schema"] = json!("not a uri");
+        report["runs"][0]["results"][0]["guid"] = json!("not-a-guid");
+        let rule = report["runs"][0]["tool"]["driver"]["rules"][0].clone();
+        report["runs"][0]["tool"]["driver"]["rules"] = json!([rule.clone(), rule]);
+
+        let errors = validate_schema(&report);
+        let pointers = pointers(&errors);
+
+        assert!(pointers.contains(&"/$schema"));
+        assert!(pointers.contains(&"/runs/0/results/0/guid"));
+        assert!(pointers.contains(&"/runs/0/tool/driver/rules"));
+    }
+
+    #[test]
+    fn test_message_requires_text_or_id() {
+        let mut report = valid_report();
+        report["runs"][0]["results"][0]["message"] = json!({});
+
+        let errors = validate_schema(&report);
+        assert_eq!(pointers(&errors), vec!["/runs/0/results/0/message"]);
+        assert!(errors[0].message.contains("'text'"));
+        assert!(errors[0].message.contains("'id'"));
+    }
+
+    #[test]
+    fn test_unknown_top_level_property() {
+        let mut report = valid_report();
+        report["extra/field"] = json!(true);
+
+        let errors = validate_schema(&report);
+        assert_eq!(pointers(&errors), vec!["/extra~1field"]);
+    }
+
+    #[test]
+    fn test_github_requires_partial_fingerprints() {
+        let mut report = valid_report();
+        report["runs"][0]["results"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("partialFingerprints");
+
+        let errors = validate_github_constraints(&report);
+        assert_eq!(pointers(&errors), vec!["/runs/0/results/0"]);
+        assert!(errors[0].message.contains("partialFingerprints"));
+    }
+
+    #[test]
+    fn test_github_rule_id_length() {
+        let mut report = valid_report();
+        report["runs"][0]["results"][0]["ruleId"] = json!("x".repeat(MAX_RULE_ID_LENGTH + 1));
+
+        let errors = validate_github_constraints(&report);
+        assert_eq!(pointers(&errors), vec!["/runs/0/results/0/ruleId"]);
+    }
+
+    #[test]
+    fn test_github_result_limit() {
+        let mut report = valid_report();
+        let result = report["runs"][0]["results"][0].clone();
+        report["runs"][0]["results"] = Value::Array(vec![result; MAX_RESULTS_PER_RUN + 1]);
+
+        let errors = validate_github_constraints(&report);
+        assert_eq!(pointers(&errors), vec!["/runs/0/results"]);
+    }
+
+    #[test]
+    fn test_check_artifact_uri() {
+        assert!(check_artifact_uri("src/main.rs").is_none());
+        assert!(check_artifact_uri("file:///home/runner/work/repo/src/main.rs").is_none());
+        assert!(check_artifact_uri("src/my%20file.rs").is_none());
+
+        assert!(check_artifact_uri("").is_some());
+        assert!(check_artifact_uri("src\\main.rs").is_some());
+        assert!(check_artifact_uri("src/my file.rs").is_some());
+        assert!(check_artifact_uri("C:/repo/main.rs").is_some());
+        assert!(check_artifact_uri("https://example.com/main.rs").is_some());
+    }
+
+    #[test]
+    fn test_display() {
+        let err = ValidationError::new("/runs/0", "missing required property 'tool'");
+        assert_eq!(err.to_string(), "/runs/0: missing required property 'tool'");
+        assert_eq!(ValidationError::new("", "bad").to_string(), "/: bad");
+    }
+}
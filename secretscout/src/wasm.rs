@@ -24,11 +24,9 @@ pub fn parse_sarif(sarif_json: &str) -> Result<JsValue, JsValue> {
 /// Extract findings from SARIF JSON string
 #[wasm_bindgen]
 pub fn extract_findings(sarif_json: &str) -> Result<JsValue, JsValue> {
-    let report =
-        sarif::parse_sarif_str(sarif_json).map_err(|e| JsValue::from_str(&e.to_string()))?;
-
-    let findings =
-        sarif::extract_findings(&report).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let findings = sarif::stream_findings(sarif_json.as_bytes())
+        .collect::<crate::error::Result<Vec<_>>>()
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
     serde_wasm_bindgen::to_value(&findings).map_err(|e| JsValue::from_str(&e.to_string()))
 }
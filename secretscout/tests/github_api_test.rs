@@ -0,0 +1,1537 @@
+//! GitHub API integration tests
+//!
+//! These tests run the GitHub integrations against a mock API server.
+
+use secretscout::{
+    config::Config,
+    error::{Error, GitHubError},
+    events::{self, EventContext, EventType, GitReference, PullRequest, Repository},
+    github::client::{GitHubClient, RetryPolicy},
+    outputs::{self, code_scanning, CheckConclusion},
+    sarif::types::DetectedSecret,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::{
+    body_partial_json, body_string_contains, header, header_regex, method, path, path_regex,
+    query_param, query_param_is_missing,
+};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const SARIF_PATH: &str = "/repos/test/repo/code-scanning/sarifs";
+
+/// Helper to build a configuration pointing at the mock server
+fn test_config(server: &MockServer, workspace: &TempDir) -> Config {
+    Config {
+        github_token: "test-token-123".to_string(),
+        github_app: None,
+        gitleaks_license: None,
+        gitleaks_version: "8.24.3".to_string(),
+        gitleaks_config: None,
+        enable_summary: false,
+        enable_annotations: false,
+        enable_upload_artifact: false,
+        artifact_name: secretscout::config::DEFAULT_ARTIFACT_NAME.to_string(),
+        artifact_retention_days: None,
+        enable_comments: false,
+        review_event: secretscout::github::ReviewEvent::Comment,
+        enable_check_run: false,
+        enable_commit_status: false,
+        issue_mode: secretscout::config::IssueMode::Off,
+        issue_labels: vec![],
+        enable_code_scanning_upload: true,
+        code_scanning_category: Some("secrets".to_string()),
+        notify_user_list: vec![],
+        notify_code_owners: false,
+        base_ref: None,
+        dry_run: false,
+        workspace_path: workspace.path().to_path_buf(),
+        event_path: PathBuf::from("/tmp/event.json"),
+        event_name: "push".to_string(),
+        repository: "test/repo".to_string(),
+        repository_owner: "test".to_string(),
+        sha: "abc123def456".to_string(),
+        git_ref: "refs/heads/main".to_string(),
+        run_id: String::new(),
+        github_api_url: server.uri(),
+        github_server_url: "https://github.com".to_string(),
+        github_graphql_url: format!("{}/graphql", server.uri()),
+    }
+}
+
+/// Helper to build the repository under test
+fn test_repository() -> Repository {
+    Repository {
+        owner: "test".to_string(),
+        name: "repo".to_string(),
+        full_name: "test/repo".to_string(),
+        html_url: "https://github.com/test/repo".to_string(),
+    }
+}
+
+/// Helper to build a push event context
+fn test_push_context() -> EventContext {
+    EventContext {
+        event_type: EventType::Push,
+        repository: test_repository(),
+        base_ref: "abc123def456".to_string(),
+        head_ref: "abc123def456".to_string(),
+        commits: vec![],
+        pull_request: None,
+        inputs: None,
+    }
+}
+
+/// Helper to build a pull request event context
+fn test_pull_request_context() -> EventContext {
+    EventContext {
+        event_type: EventType::PullRequest,
+        repository: test_repository(),
+        base_ref: "base123".to_string(),
+        head_ref: "head456".to_string(),
+        commits: vec![],
+        pull_request: Some(PullRequest {
+            number: 42,
+            base: GitReference {
+                sha: "base123".to_string(),
+                ref_name: "main".to_string(),
+            },
+            head: GitReference {
+                sha: "head456".to_string(),
+                ref_name: "feature-branch".to_string(),
+            },
+            fork: false,
+        }),
+        inputs: None,
+    }
+}
+
+/// Helper to mount an empty list of existing PR comments
+async fn mount_no_existing_comments(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/pulls/42/comments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(server)
+        .await;
+}
+
+/// Helper to build `count` findings in a single file
+fn test_findings(count: u32) -> Vec<DetectedSecret> {
+    (1..=count)
+        .map(|line| DetectedSecret {
+            rule_id: "generic-api-key".to_string(),
+            file_path: "src/config.rs".to_string(),
+            line_number: line,
+            commit_sha: "abc123def456".to_string(),
+            author: "Test Author".to_string(),
+            email: "test@example.com".to_string(),
+            date: "2025-10-16T12:00:00Z".to_string(),
+            fingerprint: format!("abc123def456:src/config.rs:generic-api-key:{}", line),
+            owners: Vec::new(),
+        })
+        .collect()
+}
+
+/// Helper to write a minimal SARIF report into the workspace
+fn write_sarif(workspace: &TempDir) {
+    let sarif = serde_json::json!({
+        "version": "2.1.0",
+        "runs": [{ "tool": { "driver": { "name": "gitleaks" } }, "results": [] }]
+    });
+    fs::write(workspace.path().join("results.sarif"), sarif.to_string()).unwrap();
+}
+
+#[tokio::test]
+async fn test_code_scanning_upload_completes() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    write_sarif(&workspace);
+
+    Mock::given(method("POST"))
+        .and(path(SARIF_PATH))
+        .and(body_partial_json(serde_json::json!({
+            "commit_sha": "abc123def456",
+            "ref": "refs/heads/main",
+            "tool_name": "gitleaks"
+        })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(serde_json::json!({
+            "id": "47177e22-5596-11eb-80a1-c1e54ef945c6",
+            "url": "https://api.github.com/repos/test/repo/code-scanning/sarifs/47177e22"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!(
+            "{}/47177e22-5596-11eb-80a1-c1e54ef945c6",
+            SARIF_PATH
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "processing_status": "complete",
+            "analyses_url": "https://api.github.com/repos/test/repo/code-scanning/analyses?sarif_id=47177e22"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = test_config(&server, &workspace);
+    let status = code_scanning::upload_sarif_report(
+        &config,
+        &api_client(&config).await,
+        &test_push_context(),
+        &config.sarif_path(),
+        Duration::from_millis(10),
+    )
+    .await
+    .unwrap();
+
+    assert!(status.analyses_url.unwrap().contains("sarif_id=47177e22"));
+}
+
+#[tokio::test]
+async fn test_code_scanning_upload_uses_pull_request_head() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    write_sarif(&workspace);
+
+    // GITHUB_SHA and GITHUB_REF name the base branch on pull_request_target
+    Mock::given(method("POST"))
+        .and(path(SARIF_PATH))
+        .and(body_partial_json(serde_json::json!({
+            "commit_sha": "head456",
+            "ref": "refs/pull/42/head",
+        })))
+        .respond_with(
+            ResponseTemplate::new(202).set_body_json(serde_json::json!({ "id": "pr-upload" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("{}/pr-upload", SARIF_PATH)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "processing_status": "complete"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = Config {
+        event_name: "pull_request_target".to_string(),
+        ..test_config(&server, &workspace)
+    };
+    let context = EventContext {
+        event_type: EventType::PullRequestTarget,
+        ..test_pull_request_context()
+    };
+    code_scanning::upload_sarif_report(
+        &config,
+        &api_client(&config).await,
+        &context,
+        &config.sarif_path(),
+        Duration::from_millis(10),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_code_scanning_upload_rejected() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    write_sarif(&workspace);
+
+    Mock::given(method("POST"))
+        .and(path(SARIF_PATH))
+        .respond_with(
+            ResponseTemplate::new(202).set_body_json(serde_json::json!({ "id": "rejected-id" })),
+        )
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("{}/rejected-id", SARIF_PATH)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "processing_status": "failed",
+            "errors": ["locationFromSarifResult: expected artifact location"]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = test_config(&server, &workspace);
+    let error = code_scanning::upload_sarif_report(
+        &config,
+        &api_client(&config).await,
+        &test_push_context(),
+        &config.sarif_path(),
+        Duration::from_millis(10),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(error
+        .to_string()
+        .contains("locationFromSarifResult: expected artifact location"));
+}
+
+#[tokio::test]
+async fn test_check_run_annotations_are_batched() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/check-runs"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "SecretScout",
+            "head_sha": "abc123def456",
+            "status": "in_progress"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 4 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/repos/test/repo/check-runs/4"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 4 })))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let config = test_config(&server, &workspace);
+    let id = outputs::publish_check_run(
+        &config,
+        &api_client(&config).await,
+        &test_push_context(),
+        &test_findings(120),
+        120,
+        CheckConclusion::Failure,
+    )
+    .await
+    .unwrap();
+    assert_eq!(id, 4);
+
+    let updates: Vec<serde_json::Value> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.method.as_str() == "PATCH")
+        .map(|r| serde_json::from_slice(&r.body).unwrap())
+        .collect();
+
+    let batch_sizes: Vec<usize> = updates
+        .iter()
+        .map(|u| u["output"]["annotations"].as_array().unwrap().len())
+        .collect();
+    assert_eq!(batch_sizes, vec![50, 50, 20]);
+
+    assert!(updates[0].get("conclusion").is_none());
+    assert_eq!(updates[2]["status"], "completed");
+    assert_eq!(updates[2]["conclusion"], "failure");
+    assert_eq!(updates[2]["output"]["title"], "120 secret(s) detected");
+}
+
+#[tokio::test]
+async fn test_pr_comments_posted_as_single_review() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    mount_no_existing_comments(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/pulls/42/reviews"))
+        .and(body_partial_json(serde_json::json!({
+            "commit_id": "head456",
+            "event": "COMMENT"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 80 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/pulls/42/comments"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 1 })))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let config = test_config(&server, &workspace);
+    let posted = outputs::post_pr_comments(
+        &config,
+        &api_client(&config).await,
+        &test_pull_request_context(),
+        &test_findings(40),
+    )
+    .await
+    .unwrap();
+    assert_eq!(posted, 40);
+
+    let requests = server.received_requests().await.unwrap();
+    let review = requests
+        .iter()
+        .find(|r| r.url.path().ends_with("/reviews"))
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&review.body).unwrap();
+    assert_eq!(body["comments"].as_array().unwrap().len(), 40);
+    assert_eq!(body["comments"][0]["path"], "src/config.rs");
+    assert_eq!(body["comments"][0]["line"], 1);
+}
+
+#[tokio::test]
+async fn test_pr_comments_mention_only_valid_users_and_teams() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    mount_no_existing_comments(&server).await;
+
+    let account = |login: &str, kind: &str| {
+        ResponseTemplate::new(200)
+            .set_body_json(serde_json::json!({ "login": login, "type": kind }))
+    };
+    Mock::given(method("GET"))
+        .and(path("/users/octocat"))
+        .respond_with(account("octocat", "User"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/users/acme"))
+        .respond_with(account("acme", "Organization"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/users/octocta"))
+        .respond_with(
+            ResponseTemplate::new(404).set_body_json(serde_json::json!({ "message": "Not Found" })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/orgs/acme/teams/security"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 1 })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/orgs/acme/teams/securty"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/pulls/42/reviews"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 80 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = test_config(&server, &workspace);
+    config.notify_user_list = [
+        "@octocat",
+        "@octocta",
+        "@acme",
+        "@acme/security",
+        "@acme/securty",
+    ]
+    .iter()
+    .map(|u| u.to_string())
+    .collect();
+
+    outputs::post_pr_comments(
+        &config,
+        &api_client(&config).await,
+        &test_pull_request_context(),
+        &test_findings(2),
+    )
+    .await
+    .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let review = requests
+        .iter()
+        .find(|r| r.url.path().ends_with("/reviews"))
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&review.body).unwrap();
+    for comment in body["comments"].as_array().unwrap() {
+        assert!(comment["body"]
+            .as_str()
+            .unwrap()
+            .contains("**CC:** @octocat @acme/security\n"));
+    }
+}
+
+#[tokio::test]
+async fn test_pr_comments_mention_code_owners() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    mount_no_existing_comments(&server).await;
+    fs::create_dir(workspace.path().join(".github")).unwrap();
+    fs::write(
+        workspace.path().join(".github/CODEOWNERS"),
+        "*.rs @rustacean security@example.com\n",
+    )
+    .unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/users/rustacean"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "login": "rustacean", "type": "User" })),
+        )
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/pulls/42/reviews"))
+        .and(body_string_contains("**CC:** @rustacean\\n"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 80 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = test_config(&server, &workspace);
+    config.notify_user_list = vec!["@fallback".to_string()];
+    config.notify_code_owners = true;
+
+    outputs::post_pr_comments(
+        &config,
+        &api_client(&config).await,
+        &test_pull_request_context(),
+        &test_findings(1),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_rejected_review_falls_back_to_individual_comments() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    mount_no_existing_comments(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/pulls/42/reviews"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+            "message": "Unprocessable Entity",
+            "errors": ["Line could not be resolved"]
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/pulls/42/comments"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 1 })))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let config = test_config(&server, &workspace);
+    let posted = outputs::post_pr_comments(
+        &config,
+        &api_client(&config).await,
+        &test_pull_request_context(),
+        &test_findings(3),
+    )
+    .await
+    .unwrap();
+    assert_eq!(posted, 3);
+}
+
+#[tokio::test]
+async fn test_stale_comments_are_resolved() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+
+    let body = |fingerprint: &str| {
+        secretscout::github::build_comment_body("generic-api-key", "abc123def456", fingerprint, &[])
+    };
+    let bot = serde_json::json!({ "login": "github-actions[bot]", "type": "Bot" });
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/pulls/42/comments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            { "id": 10, "node_id": "PRRC_10", "in_reply_to_id": null, "user": bot,
+              "body": body("abc123def456:src/config.rs:generic-api-key:1") },
+            { "id": 11, "node_id": "PRRC_11", "in_reply_to_id": null, "user": bot,
+              "body": body("abc123def456:src/old.rs:generic-api-key:5") },
+            { "id": 12, "node_id": "PRRC_12", "in_reply_to_id": null, "user": bot,
+              "body": body("abc123def456:src/gone.rs:generic-api-key:9") },
+            { "id": 13, "node_id": "PRRC_13", "in_reply_to_id": null, "user": bot,
+              "body": body("abc123def456:src/acked.rs:generic-api-key:3") },
+            { "id": 14, "node_id": "PRRC_14", "in_reply_to_id": null,
+              "user": { "login": "reviewer", "type": "User" },
+              "body": format!("> {}", body("abc123def456:src/quoted.rs:generic-api-key:4")) }
+        ])))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/pulls/42/comments/11/replies"))
+        .and(body_partial_json(
+            serde_json::json!({ "body": "✅ Resolved in head456" }),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 20 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/pulls/42/comments/12/replies"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 21 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("reviewThreads"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "repository": { "pullRequest": { "reviewThreads": {
+                "pageInfo": { "hasNextPage": false, "endCursor": null },
+                "nodes": [
+                    { "id": "PRRT_10", "isResolved": false,
+                      "comments": { "nodes": [{ "databaseId": 10 }] } },
+                    { "id": "PRRT_11", "isResolved": false,
+                      "comments": { "nodes": [{ "databaseId": 11 }] } }
+                ]
+            } } } }
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("resolveReviewThread"))
+        .and(body_string_contains("PRRT_11"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "resolveReviewThread": { "thread": { "id": "PRRT_11" } } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("minimizeComment"))
+        .and(body_string_contains("PRRC_12"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "minimizeComment": { "minimizedComment": { "isMinimized": true } } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Only the expected replies are accepted
+    Mock::given(method("POST"))
+        .and(path_regex(
+            r"/repos/test/repo/pulls/42/comments/1[34]/replies",
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 22 })))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let config = test_config(&server, &workspace);
+    let acknowledged =
+        std::collections::HashSet::from(
+            ["abc123def456:src/acked.rs:generic-api-key:3".to_string()],
+        );
+    let resolved = outputs::resolve_stale_comments(
+        &config,
+        &api_client(&config).await,
+        &test_pull_request_context(),
+        &test_findings(1),
+        &acknowledged,
+    )
+    .await
+    .unwrap();
+    assert_eq!(resolved, 2);
+}
+
+#[tokio::test]
+async fn test_summary_comment_is_updated_in_place() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/issues/42/comments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            { "id": 4, "body": "<!-- secretscout:summary -->\nNot the real summary", "user": { "login": "someone", "type": "User" } },
+            { "id": 5, "body": "Looks good to me", "user": { "login": "someone", "type": "User" } },
+            { "id": 6, "body": "<!-- secretscout:summary -->\n## 🛑 Gitleaks detected secrets 🛑", "user": { "login": "github-actions[bot]", "type": "Bot" } },
+        ])))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/repos/test/repo/issues/comments/6"))
+        .and(body_string_contains("No leaks detected"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 6 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/issues/42/comments"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 7 })))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let config = test_config(&server, &workspace);
+    outputs::upsert_summary_comment(
+        &config,
+        &api_client(&config).await,
+        &test_pull_request_context(),
+        &[],
+        0,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_dry_run_reads_but_does_not_write() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/issues/42/comments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 7 })))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let config = Config {
+        dry_run: true,
+        ..test_config(&server, &workspace)
+    };
+    outputs::upsert_summary_comment(
+        &config,
+        &api_client(&config).await,
+        &test_pull_request_context(),
+        &[],
+        0,
+    )
+    .await
+    .unwrap();
+    outputs::publish_commit_status(
+        &config,
+        &api_client(&config).await,
+        &test_repository(),
+        "abc123def456",
+        outputs::ScanOutcome::Clean,
+        None,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_summary_comment_is_posted_once() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/issues/42/comments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/issues/42/comments"))
+        .and(body_string_contains("<!-- secretscout:summary -->"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 7 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = test_config(&server, &workspace);
+    let context = test_pull_request_context();
+    outputs::upsert_summary_comment(
+        &config,
+        &api_client(&config).await,
+        &context,
+        &test_findings(2),
+        2,
+    )
+    .await
+    .unwrap();
+
+    // Without findings and without an existing comment nothing is posted
+    outputs::upsert_summary_comment(&config, &api_client(&config).await, &context, &[], 0)
+        .await
+        .unwrap();
+}
+
+/// Helper to write an issue_comment event with a comment by `login`
+fn write_comment_event(workspace: &TempDir, login: &str, body: &str) -> PathBuf {
+    let event_path = workspace.path().join("event.json");
+    let event = serde_json::json!({
+        "action": "created",
+        "issue": { "number": 42, "pull_request": { "url": "https://api.github.com/x" } },
+        "comment": { "id": 9, "body": body, "user": { "login": login, "type": "User" } },
+        "repository": {
+            "name": "repo",
+            "owner": { "login": "test" },
+            "full_name": "test/repo",
+            "html_url": "https://github.com/test/repo"
+        }
+    });
+    fs::write(&event_path, event.to_string()).unwrap();
+    event_path
+}
+
+#[tokio::test]
+async fn test_chatops_commands_require_write_permission() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    let fingerprint = "abc123def456:config/app.env:generic-api-key:3";
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/collaborators/outsider/permission"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "permission": "read" })),
+        )
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/collaborators/maintainer/permission"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "permission": "write" })),
+        )
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/issues/42/comments"))
+        .and(body_string_contains(
+            "@outsider only users with write access",
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 10 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/issues/42/comments"))
+        .and(body_string_contains(
+            "Gitleaks rule `generic-api-key` matched line 3",
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 11 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = test_config(&server, &workspace);
+    config.event_name = "issue_comment".to_string();
+
+    let command = format!("/secretscout explain {}", fingerprint);
+    config.event_path = write_comment_event(&workspace, "outsider", &command);
+    assert_eq!(secretscout::chatops::run(&config).await.unwrap(), 0);
+
+    config.event_path = write_comment_event(&workspace, "maintainer", &command);
+    assert_eq!(secretscout::chatops::run(&config).await.unwrap(), 0);
+
+    // Comments without a command are left alone
+    config.event_path = write_comment_event(&workspace, "maintainer", "Thanks!");
+    assert_eq!(secretscout::chatops::run(&config).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_acknowledgements_need_write_permission() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    let findings = test_findings(2);
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/issues/42/comments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": 5,
+                "user": { "login": "maintainer", "type": "User" },
+                "body": format!("/secretscout ignore {} test fixture", findings[0].fingerprint),
+            },
+            {
+                "id": 6,
+                "user": { "login": "outsider", "type": "User" },
+                "body": format!("/secretscout ignore {} trust me", findings[1].fingerprint),
+            },
+        ])))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/collaborators/maintainer/permission"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "permission": "admin" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/collaborators/outsider/permission"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = test_config(&server, &workspace);
+    let acknowledged = secretscout::chatops::acknowledged_fingerprints(
+        &config,
+        &api_client(&config).await,
+        &test_pull_request_context(),
+    )
+    .await;
+
+    assert!(acknowledged.contains(&findings[0].fingerprint));
+    assert!(!acknowledged.contains(&findings[1].fingerprint));
+}
+
+#[tokio::test]
+async fn test_pr_commits_follow_link_headers() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+
+    let commits = |range: std::ops::Range<u32>| {
+        range
+            .map(|i| {
+                serde_json::json!({
+                    "sha": format!("sha{}", i),
+                    "commit": {
+                        "message": format!("commit {}", i),
+                        "author": { "name": "Test Author", "email": "test@example.com" }
+                    }
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/pulls/42/commits"))
+        .and(query_param("per_page", "100"))
+        .and(query_param_is_missing("page"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header(
+                    "link",
+                    format!(
+                        "<{}/repos/test/repo/pulls/42/commits?per_page=100&page=2>; rel=\"next\"",
+                        server.uri()
+                    )
+                    .as_str(),
+                )
+                .set_body_json(commits(0..100)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/pulls/42/commits"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(commits(100..130)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = test_config(&server, &workspace);
+    let fetched = secretscout::github::fetch_pr_commits(
+        &config,
+        &api_client(&config).await,
+        &test_repository(),
+        42,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(fetched.len(), 130);
+    assert_eq!(fetched[0].sha, "sha0");
+    assert_eq!(fetched[129].sha, "sha129");
+}
+
+/// Helper to run git in a test repository
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_AUTHOR_NAME", "Test Author")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "Test Author")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+#[tokio::test]
+async fn test_large_pr_commits_listed_from_local_git() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+
+    git(workspace.path(), &["init", "-q"]);
+    git(
+        workspace.path(),
+        &["commit", "-q", "--allow-empty", "-m", "base"],
+    );
+    let base = git(workspace.path(), &["rev-parse", "HEAD"]);
+    for i in 0..3 {
+        let message = format!("change {}", i);
+        git(
+            workspace.path(),
+            &["commit", "-q", "--allow-empty", "-m", &message],
+        );
+    }
+    let head = git(workspace.path(), &["rev-parse", "HEAD"]);
+
+    // The API would only list 250 of these, so it must not be called
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/pulls/42/commits"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let event_path = workspace.path().join("event.json");
+    let event = serde_json::json!({
+        "number": 42,
+        "pull_request": {
+            "number": 42,
+            "commits": 300,
+            "base": { "ref": "main", "sha": base },
+            "head": { "ref": "feature-branch", "sha": head }
+        },
+        "repository": {
+            "name": "repo",
+            "owner": { "login": "test" },
+            "full_name": "test/repo",
+            "html_url": "https://github.com/test/repo"
+        }
+    });
+    fs::write(&event_path, event.to_string()).unwrap();
+
+    let mut config = test_config(&server, &workspace);
+    config.event_name = "pull_request".to_string();
+    config.event_path = event_path;
+
+    let context = events::parse_event_context(&config).await.unwrap();
+    assert_eq!(context.commits.len(), 3);
+    assert_eq!(context.commits[0].message, "change 0");
+    assert_eq!(context.head_ref, head);
+}
+
+#[tokio::test]
+async fn test_enterprise_server_urls() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/repos/test/repo/pulls/42/comments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{ "id": 1 }])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = test_config(&server, &workspace);
+    config.github_api_url = format!("{}/api/v3", server.uri());
+    config.github_server_url = "https://ghe.example.com".to_string();
+
+    let comments = secretscout::github::fetch_pr_comments(
+        &config,
+        &api_client(&config).await,
+        &test_repository(),
+        42,
+    )
+    .await
+    .unwrap();
+    assert_eq!(comments.len(), 1);
+
+    // Schedule events carry no repository, so links come from the server URL
+    let event_path = workspace.path().join("event.json");
+    fs::write(&event_path, "{}").unwrap();
+    config.event_name = "schedule".to_string();
+    config.event_path = event_path;
+
+    let context = events::parse_event_context(&config).await.unwrap();
+    assert_eq!(
+        context.repository.html_url,
+        "https://ghe.example.com/test/repo"
+    );
+}
+
+#[tokio::test]
+async fn test_commit_status_reports_findings() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/statuses/abc123def456"))
+        .and(body_partial_json(serde_json::json!({
+            "state": "failure",
+            "context": "secretscout/scan",
+            "description": "3 secrets found",
+            "target_url": "https://ci.example.com/job/7",
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 1 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = test_config(&server, &workspace);
+    outputs::publish_commit_status(
+        &config,
+        &api_client(&config).await,
+        &test_repository(),
+        "abc123def456",
+        outputs::ScanOutcome::Leaks(Some(3)),
+        Some("https://ci.example.com/job/7"),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_issue_sync_after_full_scan() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    fs::create_dir(workspace.path().join(".github")).unwrap();
+    fs::write(
+        workspace.path().join(".github/CODEOWNERS"),
+        "*.rs @rustacean\n",
+    )
+    .unwrap();
+
+    let findings = test_findings(3);
+    let marker =
+        |finding: &DetectedSecret| format!("<!-- secretscout:finding {} -->", finding.fingerprint);
+
+    // Gone finding (#1), finding that came back (#2), dismissed finding (#3)
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/issues"))
+        .and(query_param("labels", "secretscout"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            { "number": 1, "state": "open", "body": "<!-- secretscout:finding gone -->" },
+            { "number": 2, "state": "closed", "state_reason": "completed", "body": marker(&findings[0]) },
+            { "number": 3, "state": "closed", "state_reason": "not_planned", "body": marker(&findings[1]) },
+            { "number": 4, "state": "open", "body": "", "pull_request": {} },
+        ])))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/issues"))
+        .and(body_partial_json(serde_json::json!({
+            "labels": ["secretscout", "security"],
+            "assignees": ["rustacean"],
+        })))
+        .and(body_string_contains(&findings[2].fingerprint))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "number": 5 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/repos/test/repo/issues/1"))
+        .and(body_partial_json(
+            serde_json::json!({ "state": "closed", "state_reason": "completed" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/repos/test/repo/issues/2"))
+        .and(body_partial_json(serde_json::json!({ "state": "open" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/repos/test/repo/issues/3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    for number in [1, 2] {
+        Mock::given(method("POST"))
+            .and(path(format!("/repos/test/repo/issues/{}/comments", number)))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let mut config = test_config(&server, &workspace);
+    config.issue_mode = secretscout::config::IssueMode::PerFinding;
+    config.issue_labels = vec!["security".to_string()];
+
+    // Schedule scans cover the full history, so gone findings are closed
+    let mut context = test_push_context();
+    context.event_type = EventType::Schedule;
+    context.base_ref = String::new();
+    context.head_ref = String::new();
+
+    let sync = outputs::sync_issues(
+        &config,
+        &api_client(&config).await,
+        &context,
+        &findings,
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(sync.opened, 1);
+    assert_eq!(sync.reopened, 1);
+    assert_eq!(sync.updated, 0);
+    assert_eq!(sync.closed, 1);
+}
+
+/// Helper to generate a throwaway GitHub App private key
+fn generate_app_key() -> String {
+    let output = std::process::Command::new("openssl")
+        .args(["genrsa", "2048"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "openssl genrsa failed");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[tokio::test]
+async fn test_github_app_installation_token() {
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/installation"))
+        .and(header_regex(
+            "authorization",
+            r"^Bearer eyJ[\w-]+\.[\w-]+\.[\w-]+$",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 7 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let expires_at = chrono::Utc::now() + chrono::Duration::hours(1);
+    Mock::given(method("POST"))
+        .and(path("/app/installations/7/access_tokens"))
+        .and(body_partial_json(
+            serde_json::json!({ "repositories": ["repo"] }),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "token": "ghs_installation",
+            "expires_at": expires_at.to_rfc3339(),
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Both requests use the cached installation token
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo/pulls/42/comments"))
+        .and(header("authorization", "Bearer ghs_installation"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let mut config = test_config(&server, &workspace);
+    config.github_token = String::new();
+    config.github_app = Config::parse_github_app(
+        Some("test-app-installation".to_string()),
+        Some(generate_app_key()),
+        None,
+    )
+    .unwrap();
+
+    for _ in 0..2 {
+        secretscout::github::fetch_pr_comments(
+            &config,
+            &api_client(&config).await,
+            &test_repository(),
+            42,
+        )
+        .await
+        .unwrap();
+    }
+}
+
+/// Helper to build a client that retries quickly
+/// Helper to create the client the action would use for `config`
+async fn api_client(config: &Config) -> GitHubClient {
+    secretscout::github::create_client(config).await.unwrap()
+}
+
+fn fast_client() -> GitHubClient {
+    GitHubClient::new("test-token-123")
+        .unwrap()
+        .with_retry_policy(RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(5),
+        })
+}
+
+#[tokio::test]
+async fn test_client_waits_out_secondary_rate_limit() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/issues"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "1")
+                .set_body_json(serde_json::json!({
+                    "message": "You have exceeded a secondary rate limit."
+                })),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/issues"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 1 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let started = std::time::Instant::now();
+    let response = fast_client()
+        .post(
+            &format!("{}/repos/test/repo/issues", server.uri()),
+            &serde_json::json!({ "title": "test" }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response["id"], 1);
+    assert!(started.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_client_fails_fast_on_exhausted_primary_rate_limit() {
+    let server = MockServer::start().await;
+    let reset = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        + 3600;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", reset.to_string().as_str())
+                .set_body_json(serde_json::json!({ "message": "API rate limit exceeded" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let error = fast_client()
+        .get(&format!("{}/repos/test/repo", server.uri()))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::GitHub(GitHubError::RateLimitExceeded)
+    ));
+}
+
+#[tokio::test]
+async fn test_client_does_not_retry_forbidden() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("x-ratelimit-remaining", "4999")
+                .set_body_json(serde_json::json!({
+                    "message": "Resource not accessible by integration"
+                })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let error = fast_client()
+        .get(&format!("{}/repos/test/repo", server.uri()))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::GitHub(GitHubError::AuthenticationFailed(ref m)) if m.contains("not accessible")
+    ));
+}
+
+#[tokio::test]
+async fn test_client_retries_server_errors_only_when_idempotent() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo"))
+        .respond_with(ResponseTemplate::new(502))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/test/repo"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 7 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/repos/test/repo/issues"))
+        .respond_with(ResponseTemplate::new(502))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = fast_client();
+    let repo = client
+        .get(&format!("{}/repos/test/repo", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(repo["id"], 7);
+
+    let error = client
+        .post(
+            &format!("{}/repos/test/repo/issues", server.uri()),
+            &serde_json::json!({ "title": "test" }),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::GitHub(GitHubError::RequestFailed { status: 502, .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_upload_artifact() {
+    use base64::Engine;
+    use sha2::{Digest, Sha256};
+
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    let sarif_path = workspace.path().join("results.sarif");
+    fs::write(&sarif_path, r#"{"runs":[]}"#).unwrap();
+
+    let service = "/twirp/github.actions.results.api.v1.ArtifactService";
+    let claims = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .encode(r#"{"scp":"Actions.Results:run-1:job-1"}"#);
+    let runtime_token = format!("e30.{}.signature", claims);
+
+    Mock::given(method("POST"))
+        .and(path(format!("{}/CreateArtifact", service)))
+        .and(header("authorization", format!("Bearer {}", runtime_token)))
+        .and(body_partial_json(serde_json::json!({
+            "workflow_run_backend_id": "run-1",
+            "workflow_job_run_backend_id": "job-1",
+            "name": "gitleaks-results.sarif",
+            "version": 4,
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ok": true,
+            "signedUploadUrl": format!("{}/blob/upload?sig=abc", server.uri()),
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/blob/upload"))
+        .and(query_param("sig", "abc"))
+        .and(header("x-ms-blob-type", "BlockBlob"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{}/FinalizeArtifact", service)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ok": true,
+            "artifactId": "1234",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = outputs::artifact::ArtifactClient::new(&server.uri(), &runtime_token).unwrap();
+    let artifact_id = client
+        .upload_file("gitleaks-results.sarif", &sarif_path, Some(5))
+        .await
+        .unwrap();
+    assert_eq!(artifact_id, "1234");
+
+    let requests = server.received_requests().await.unwrap();
+    let create: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert!(create["expires_at"].as_str().unwrap().ends_with('Z'));
+
+    let archive = &requests[1].body;
+    let finalize: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+    assert_eq!(finalize["size"], archive.len().to_string());
+    assert_eq!(
+        finalize["hash"],
+        format!("sha256:{:x}", Sha256::digest(archive))
+    );
+}
+
+#[tokio::test]
+async fn test_upload_artifact_rejected() {
+    use base64::Engine;
+
+    let server = MockServer::start().await;
+    let workspace = TempDir::new().unwrap();
+    let sarif_path = workspace.path().join("results.sarif");
+    fs::write(&sarif_path, "{}").unwrap();
+
+    Mock::given(method("POST"))
+        .and(path(
+            "/twirp/github.actions.results.api.v1.ArtifactService/CreateArtifact",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "ok": false })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let claims = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .encode(r#"{"scp":"Actions.Results:run-1:job-1"}"#);
+    let client =
+        outputs::artifact::ArtifactClient::new(&server.uri(), &format!("e30.{}.sig", claims))
+            .unwrap();
+    let error = client
+        .upload_file("gitleaks-results.sarif", &sarif_path, None)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::GitHub(GitHubError::ArtifactRejected(_))
+    ));
+}
@@ -296,8 +296,9 @@ async fn test_parse_push_event() {
     assert!(matches!(context.event_type, events::EventType::Push));
     assert_eq!(context.repository.name, "repo");
     assert_eq!(context.repository.owner, "test");
-    // For push events, base_ref comes from "after" and head_ref from "after"
-    assert_eq!(context.base_ref, "abc123def456");
+    // The push created the branch, so there is no base and everything
+    // reachable from "after" is scanned
+    assert_eq!(context.base_ref, "");
     assert_eq!(context.head_ref, "abc123def456");
     assert_eq!(context.commits.len(), 1);
     assert_eq!(context.commits[0].sha, "abc123def456");
@@ -455,6 +456,7 @@ fn test_build_log_opts_for_different_events() {
         head_ref: "def456".to_string(),
         commits: vec![],
         pull_request: None,
+        inputs: None,
     };
 
     let log_opts = events::build_log_opts(&push_context);
@@ -470,6 +472,7 @@ fn test_build_log_opts_for_different_events() {
         head_ref: "refs/heads/main".to_string(),
         commits: vec![],
         pull_request: None,
+        inputs: None,
     };
 
     let log_opts = events::build_log_opts(&dispatch_context);